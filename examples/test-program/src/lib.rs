@@ -131,4 +131,13 @@ mod test {
         // Drop the connection and all the noise created in the local database.
         db.close().await;
     }
+
+    #[test]
+    fn table_and_column_names() {
+        assert_eq!(super::StoredEvent::table_name(), "events");
+        assert_eq!(
+            super::StoredEvent::column_names(),
+            &["topic1", "remaining", "data"]
+        );
+    }
 }
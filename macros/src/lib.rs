@@ -1,48 +1,38 @@
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{self, ext, parse_macro_input, DeriveInput, Expr, ExprLit, FieldsNamed, Ident, Lit, LitStr, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{self, parse_macro_input, spanned::Spanned, Attribute, DeriveInput, Error, Expr, ExprLit, Field, FieldsNamed, GenericArgument, Ident, Lit, LitStr, Meta, MetaNameValue, PathArguments, Type, TypePath};
 
 // todo: clean code
 
-#[proc_macro_derive(DatabaseInteract, attributes(with_name, external))]
+#[proc_macro_derive(DatabaseInteract, attributes(with_name, external, column_name, skip, zephyr))]
 pub fn database_interact_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_database_interact(input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    }
+}
+
+fn expand_database_interact(input: DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
-    
-    let with_name_attr = input.attrs.iter().find_map(|attr| {
-        if attr.path().is_ident("with_name") {
-            let value: Expr = attr.parse_args().unwrap();
-            if let Expr::Lit(ExprLit { lit, .. }) = value {
-                if let Lit::Str(value) = lit {
-                    return Some(value.value())
-                } else {
-                    panic!("Invalid lit type")
-                }
-            } else {
-                panic!("Invalid type")
-            }
-        } else {
-            panic!("No table name provided")
-        } 
-    }).expect("No with_name attribute");
-
-    let external = input.attrs.iter().find_map(|attr| {
-        if attr.path().is_ident("external") {
-            let value: Expr = attr.parse_args().unwrap();
-            if let Expr::Lit(ExprLit { lit, .. }) = value {
-                if let Lit::Str(value) = lit {
-                    return Some(value.value().parse::<i64>().expect("Cannot parse external to i64"))
-                } else {
-                    panic!("Invalid lit type")
-                }
-            } else {
-                panic!("Invalid type")
-            }
-        } else {
-            return None
-        } 
-    });
+
+    let with_name_attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("with_name"))
+        .ok_or_else(|| Error::new(struct_name.span(), "No table name provided, add a #[with_name(\"...\")] attribute"))?;
+    let with_name_attr = str_attribute(with_name_attr)?;
+
+    let external = match input.attrs.iter().find(|attr| attr.path().is_ident("external")) {
+        Some(attr) => {
+            let value = str_attribute(attr)?;
+            Some(value.parse::<i64>().map_err(|_| Error::new_spanned(attr, "Cannot parse external to i64"))?)
+        }
+        None => None,
+    };
 
     let (is_external, external) = {
         if let Some(external) = external {
@@ -52,41 +42,70 @@ pub fn database_interact_derive(input: TokenStream) -> TokenStream {
         }
     };
 
-    let idents: Vec<(Ident, usize, Ident)> = match input.data {
-        syn::Data::Struct(s) => match s.fields {
+    let fields: Vec<DbField> = match &input.data {
+        syn::Data::Struct(s) => match &s.fields {
             syn::Fields::Named(FieldsNamed { named, .. }) => {
-                named.iter().enumerate().map(|(idx, field)| {
-                    let Type::Path(path) = &field.ty else {
-                        panic!("unsupported field type")
+                let mut index = 0;
+                let mut fields = Vec::new();
+
+                for field in named {
+                    let ident = field.ident.clone().unwrap();
+                    let FieldAttributes { rename, skip, primary_key, indexed } = field_attributes(field)?;
+
+                    let (field_type, optional) = match &field.ty {
+                        Type::Path(path) => match option_inner_type(path) {
+                            Some(inner) => (inner, true),
+                            None => (path.path.segments.last().unwrap().ident.clone(), false),
+                        },
+                        // The type of skipped fields is never used.
+                        _ if skip => (ident.clone(), false),
+                        _ => return Err(unsupported_type(field)),
                     };
 
-                    (field.ident.clone().unwrap(), idx, path.path.segments[0].ident.clone())
+                    if !skip && !is_supported(&field_type.to_string()) {
+                        return Err(unsupported_type(field));
+                    }
+
+                    fields.push(DbField {
+                        column: rename.unwrap_or_else(|| ident.to_string()),
+                        ident,
+                        index,
+                        field_type,
+                        optional,
+                        skip,
+                        primary_key,
+                        indexed,
+                    });
+
+                    if !skip {
+                        index += 1;
+                    }
+                }
 
-                }).collect()
+                fields
             }
-            _ => panic!("Unnamed structs are not supported.")
+            _ => return Err(Error::new(struct_name.span(), "Unnamed structs are not supported.")),
         },
 
-        _ => panic!("Unsupported type.")
+        _ => return Err(Error::new(struct_name.span(), "Unsupported type, only structs can derive DatabaseInteract.")),
     };
-    let field_literals: Vec<Lit> = idents
+
+    // Fields that are mapped to a column.
+    let columns: Vec<&DbField> = fields.iter().filter(|field| !field.skip).collect();
+
+    let field_literals: Vec<Lit> = columns
         .iter()
-        .map(|ident| {
-            let field_str = LitStr::new(&ident.0.to_string(), ident.0.span());
+        .map(|field| {
+            let field_str = LitStr::new(&field.column, field.ident.span());
             Lit::Str(field_str)
         })
         .collect();
 
-    macro_rules! check_type {
-        ($t:expr, $($expected:literal),*) => {
-            matches!($t, $($expected)|*)
-        };
-    }
-    
-    let construction_code = idents.iter().map(|(ident, _, field_type)| {
-        if check_type!(field_type.to_string().as_str(), "i64", "i128", "u64", "f64", "u32", "i32", "f32", "String", "Vec") {
+    let construction_code = fields.iter().map(|field| {
+        let ident = &field.ident;
+        if field.skip {
             quote! {
-                #ident: #ident.try_into().unwrap(),
+                #ident: Default::default(),
             }
         } else {
             quote! {
@@ -95,64 +114,111 @@ pub fn database_interact_derive(input: TokenStream) -> TokenStream {
         }
     });
 
-    let deser_code = idents.iter().map(|(ident, index, field_type)| {
-        let field_string = field_type.to_string();
-        let field_str = field_string.as_str();
-        if check_type!(field_type.to_string().as_str(), "i64", "i128", "u64", "f64", "u32", "i32", "f32", "String", "Vec") {
-            quote! {
-                let bytes = row.row.get(#index).unwrap();
-                let #ident = bincode::deserialize::<ZephyrVal>(&bytes.0).unwrap();
-            
-            }
-        } else if check_type!(field_str, "ScVal", "Hash") {
+    let deser_code = columns.iter().map(|field| {
+        let ident = &field.ident;
+        let index = field.index;
+        let deserialize = deserialize_value(&field.field_type.to_string(), &field.column, index);
+
+        if field.optional {
+            // Empty blobs are NULL columns.
             quote! {
-                let bytes = row.row.get(#index).unwrap();
-                let #ident = ReadXdr::from_xdr(&bytes.0, Limits::none()).unwrap();
-            
+                let #ident = if row.row.get(#index).unwrap().0.is_empty() {
+                    None
+                } else {
+                    Some(#deserialize)
+                };
             }
         } else {
             quote! {
-                let bytes = row.row.get(#index).unwrap();
-                let #ident = bincode::deserialize(&bytes.0).unwrap();
-                
+                let #ident = #deserialize;
             }
         }
     });
 
-    let serialize_type = idents.iter().map(|(ident, _, field_type)| {
-        if check_type!(field_type.to_string().as_str(), "i64", "i128", "u64", "f64", "u32", "i32", "f32", "String", "Vec") {
-            quote! {
-                bincode::serialize(&TryInto::<ZephyrVal>::try_into(self.#ident.clone()).unwrap()).unwrap().as_slice()
-            }
-        } else if check_type!(field_type.to_string().as_str(), "ScVal", "Hash") {
+    let serialize_type = columns.iter().map(|field| {
+        let ident = &field.ident;
+        let field_type = field.field_type.to_string();
+
+        if field.optional {
+            let serialize = serialize_value(&field_type, quote! { (*value) });
             quote! {
-                self.#ident.clone().to_xdr(Limits::none()).unwrap().as_slice()
+                match &self.#ident {
+                    Some(value) => #serialize,
+                    None => Vec::new(),
+                }.as_slice()
             }
-        }  else {
+        } else {
+            let serialize = serialize_value(&field_type, quote! { self.#ident });
             quote! {
-                bincode::serialize(&self.#ident).unwrap().as_slice()
+                #serialize.as_slice()
             }
         }
+    }).collect::<Vec<_>>();
+
+    let column_schemas = columns.iter().map(|field| {
+        let name = &field.column;
+        let primary_key = field.primary_key;
+        let indexed = field.indexed;
+
+        quote! {
+            ColumnSchema { name: #name, primary_key: #primary_key, indexed: #indexed }
+        }
     });
 
-    let serialize_type_update = idents.iter().map(|(ident, _, field_type)| {
-        if check_type!(field_type.to_string().as_str(), "i64", "i128", "u64", "f64", "u32", "i32", "f32", "String", "Vec") {
-            quote! {
-                bincode::serialize(&TryInto::<ZephyrVal>::try_into(self.#ident.clone()).unwrap()).unwrap().as_slice()
-            }
-        } else if check_type!(field_type.to_string().as_str(), "ScVal", "Hash") {
-            quote! {
-                self.#ident.clone().to_xdr(Limits::none()).unwrap().as_slice()
-            }
-        } else {
-            quote! {
-                bincode::serialize(&self.#ident).unwrap().as_slice()
-            }
+    let toml_columns = columns.iter().map(|field| {
+        let name = &field.column;
+        let primary = if field.primary_key { quote! { Some(true) } } else { quote! { None } };
+        let index = if field.indexed { quote! { Some(true) } } else { quote! { None } };
+
+        quote! {
+            TomlColumn { name: #name.to_string(), col_type: COLUMN_TYPE.to_string(), primary: #primary, index: #index }
         }
     });
 
+    // Shared by the reads with the derived and the runtime-provided external id.
+    let read_body = quote! {
+        let rows = if let Some(options) = options {
+            env.db_read_with_options(&#with_name_attr, &[#(#field_literals),*], external, conditions, options)
+        } else {
+            env.db_read(&#with_name_attr, &[#(#field_literals),*], external, conditions)
+        };
+        if rows.is_err() {
+            env.log().debug(format!("dbread failed {:?}", rows.as_ref().err()), None);
+        }
+        Self::from_rows(env, rows.unwrap())
+    };
+
     // Actual trait implementation generation
     let expanded = quote! {
+        impl #struct_name {
+            /// Returns the name of the table this structure is mapped to.
+            pub fn table_name() -> &'static str {
+                #with_name_attr
+            }
+
+            /// Returns the names of the columns this structure is mapped to.
+            pub fn column_names() -> &'static [&'static str] {
+                &[#(#field_literals),*]
+            }
+
+            /// Returns the PostgreSQL statements creating the table,
+            /// including its primary key, and its indexes.
+            pub fn ddl() -> String {
+                table_ddl(#with_name_attr, [#(#column_schemas),*])
+            }
+
+            /// Returns the SQL creating the table, same as `ddl()`.
+            pub fn create_table_sql() -> String {
+                Self::ddl()
+            }
+
+            /// Returns the table as declared in the `[[tables]]` of a
+            /// `zephyr.toml`, e.g. to generate the project configuration.
+            pub fn zephyr_toml_table() -> TomlTable {
+                TomlTable { name: #with_name_attr.to_string(), columns: vec![#(#toml_columns),*] }
+            }
+        }
+
         impl DatabaseInteract for #struct_name {
             fn read_to_rows(env: &EnvClient, conditions: Option<&[Condition]>) -> Vec<Self> where Self: Sized {
                 let external = if #is_external {
@@ -160,36 +226,319 @@ pub fn database_interact_derive(input: TokenStream) -> TokenStream {
                 } else {
                     None
                 };
+                let options: Option<&ReadOptions> = None;
 
-                let rows = env.db_read(&#with_name_attr, &[#(#field_literals),*], external, conditions);
-                if rows.is_err() {
-                    env.log().debug(format!("dbread failed {:?}", rows.as_ref().err()), None);
-                }
-                let rows = rows.unwrap();
+                #read_body
+            }
+
+            fn read_to_rows_with_options(env: &EnvClient, conditions: Option<&[Condition]>, options: &ReadOptions) -> Vec<Self> where Self: Sized {
+                let external = if #is_external {
+                    Some(#external)
+                } else {
+                    None
+                };
+                let options = Some(options);
+
+                #read_body
+            }
+
+            fn read_to_rows_external(env: &EnvClient, external_id: i64, conditions: Option<&[Condition]>) -> Vec<Self> where Self: Sized {
+                let external = Some(external_id);
+                let options: Option<&ReadOptions> = None;
+
+                #read_body
+            }
+
+            fn from_rows(env: &EnvClient, rows: TableRows) -> Vec<Self> where Self: Sized {
                 let mut result = Vec::new();
-                
-                for row in rows.rows {
+
+                for row in rows {
                     #(#deser_code)*
                     result.push(Self {
                         #(#construction_code)*
                     });
                 }
 
-
                 result
             }
 
+            fn count(env: &EnvClient, conditions: Option<&[Condition]>) -> u64 where Self: Sized {
+                env.db_count(&#with_name_attr, &[#(#field_literals),*], conditions).unwrap()
+            }
+
             fn put(&self, env: &EnvClient) {
                 env.db_write(&#with_name_attr, &[#(#field_literals),*], &[#(#serialize_type),*]).unwrap();
             }
 
             fn update(&self, env: &EnvClient, conditions: &[Condition]) {
-                env.db_update(&#with_name_attr, &[#(#field_literals),*], &[#(#serialize_type_update),*], conditions).unwrap();
+                env.db_update(&#with_name_attr, &[#(#field_literals),*], &[#(#serialize_type),*], conditions).unwrap();
+            }
+
+            fn upsert(&self, env: &EnvClient, conflict_columns: &[&str]) {
+                env.db_upsert(&#with_name_attr, &[#(#field_literals),*], &[#(#serialize_type),*], conflict_columns).unwrap();
+            }
+
+            fn delete(env: &EnvClient, conditions: &[Condition]) where Self: Sized {
+                env.db_delete(&#with_name_attr, conditions).unwrap();
             }
         }
     };
 
+    Ok(expanded)
+}
+
+/// A struct field mapped to a table column.
+struct DbField {
+    ident: Ident,
+    /// Name of the column, defaults to the field name.
+    column: String,
+    /// Position of the column in the read rows.
+    index: usize,
+    /// Type of the field, or of the inner value for `Option` fields.
+    field_type: Ident,
+    optional: bool,
+    /// Skipped fields aren't stored and are read as `Default::default()`.
+    skip: bool,
+    primary_key: bool,
+    indexed: bool,
+}
+
+/// Attributes of a field.
+#[derive(Debug, Default, PartialEq)]
+struct FieldAttributes {
+    rename: Option<String>,
+    skip: bool,
+    primary_key: bool,
+    indexed: bool,
+}
+
+/// Parses the `#[column_name = "..."]`, `#[skip]` and
+/// `#[zephyr(rename = "...", skip, primary_key, index)]` attributes of a field.
+fn field_attributes(field: &Field) -> syn::Result<FieldAttributes> {
+    let mut attributes = FieldAttributes::default();
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("column_name") {
+            let Meta::NameValue(MetaNameValue { value: Expr::Lit(ExprLit { lit: Lit::Str(value), .. }), .. }) = &attr.meta else {
+                return Err(Error::new_spanned(attr, "Expected #[column_name = \"...\"]"));
+            };
+            attributes.rename = Some(value.value());
+        } else if attr.path().is_ident("skip") {
+            attributes.skip = true;
+        } else if attr.path().is_ident("zephyr") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    attributes.rename = Some(value.value());
+                } else if meta.path.is_ident("skip") {
+                    attributes.skip = true;
+                } else if meta.path.is_ident("primary_key") {
+                    attributes.primary_key = true;
+                } else if meta.path.is_ident("index") {
+                    attributes.indexed = true;
+                } else {
+                    return Err(meta.error("expected `rename`, `skip`, `primary_key` or `index`"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Parses the string literal of a `#[name("...")]` attribute.
+fn str_attribute(attr: &Attribute) -> syn::Result<String> {
+    let value: Expr = attr.parse_args()?;
+    match value {
+        Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) => Ok(value.value()),
+        _ => Err(Error::new_spanned(attr, "Expected a string literal")),
+    }
+}
+
+/// Field types that can be stored, also accepted wrapped in `Option`.
+const SUPPORTED_TYPES: &str = "bool, i32, i64, i128, u32, u64, u128, f32, f64, String, Vec<u8>, serde_json::Value, ScVal, Hash";
 
-    TokenStream::from(expanded)
+fn is_supported(field_type: &str) -> bool {
+    matches!(field_type, "u128" | "Value") || is_zephyr_val(field_type) || is_xdr(field_type)
+}
+
+fn unsupported_type(field: &Field) -> Error {
+    let ty = &field.ty;
+    Error::new(
+        field.span(),
+        format!(
+            "Field `{}` has unsupported type `{}`. Supported types are {}. \
+             Use #[zephyr(skip)] to leave the field out of the table.",
+            field.ident.as_ref().unwrap(),
+            quote!(#ty).to_string().replace(' ', ""),
+            SUPPORTED_TYPES
+        ),
+    )
+}
+
+/// Types that are stored as a serialized `ZephyrVal`.
+fn is_zephyr_val(field_type: &str) -> bool {
+    matches!(
+        field_type,
+        "i64" | "i128" | "u64" | "f64" | "u32" | "i32" | "f32" | "String" | "Vec" | "bool"
+    )
+}
+
+/// Non-panicking `ZephyrVal` accessor returning a `Result` of the field type.
+/// Integers are coerced from any integer variant whose value fits.
+fn zephyr_val_accessor(field_type: &str) -> TokenStream2 {
+    match field_type {
+        "String" => quote! { try_as_string().map(str::to_string) },
+        "Vec" => quote! { try_as_bytes().map(<[u8]>::to_vec) },
+        "f64" | "f32" | "bool" => {
+            let method = format_ident!("try_as_{}", field_type);
+            quote! { #method() }
+        }
+        _ => {
+            let method = format_ident!("try_into_{}", field_type);
+            quote! { #method() }
+        }
+    }
 }
 
+/// Types that are stored as XDR.
+fn is_xdr(field_type: &str) -> bool {
+    matches!(field_type, "ScVal" | "Hash")
+}
+
+/// Returns the inner type of an `Option<T>` field.
+fn option_inner_type(path: &TypePath) -> Option<Ident> {
+    let segment = path.path.segments.last().unwrap();
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first() {
+        Some(GenericArgument::Type(Type::Path(inner))) => {
+            Some(inner.path.segments.last().unwrap().ident.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Code serializing `value` into the bytes stored in the column.
+fn serialize_value(field_type: &str, value: TokenStream2) -> TokenStream2 {
+    if field_type == "Value" {
+        // JSON values are stored as (possibly nested) ZephyrVal.
+        quote! {
+            bincode::serialize(&ZephyrVal::from_json(#value.clone())).unwrap()
+        }
+    } else if field_type == "u128" {
+        // u128 has no ZephyrVal variant, it's stored as big-endian bytes.
+        quote! {
+            bincode::serialize(&ZephyrVal::Bytes(#value.to_be_bytes().to_vec())).unwrap()
+        }
+    } else if is_zephyr_val(field_type) {
+        quote! {
+            bincode::serialize(&TryInto::<ZephyrVal>::try_into(#value.clone()).unwrap()).unwrap()
+        }
+    } else {
+        quote! {
+            #value.clone().to_xdr(Limits::none()).unwrap()
+        }
+    }
+}
+
+/// Code deserializing the column `bytes` into the field's value.
+fn deserialize_value(field_type: &str, column: &str, index: usize) -> TokenStream2 {
+    if field_type == "Value" {
+        quote! {
+            row.get_column::<ZephyrVal>(#index).unwrap().to_json()
+        }
+    } else if field_type == "u128" {
+        quote! {
+            u128::from_be_bytes(
+                Vec::<u8>::from(row.get_column::<ZephyrVal>(#index).unwrap())
+                    .try_into()
+                    .unwrap(),
+            )
+        }
+    } else if is_zephyr_val(field_type) {
+        let error = format!("Cannot convert column {} to {}", column, field_type);
+        let accessor = zephyr_val_accessor(field_type);
+        quote! {
+            row.get_column::<ZephyrVal>(#index).unwrap().#accessor.unwrap_or_else(|_| {
+                env.log().error(#error, None);
+                panic!(#error)
+            })
+        }
+    } else {
+        quote! {
+            row.get_column_xdr(#index).unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{field_attributes, is_supported, option_inner_type, zephyr_val_accessor, FieldAttributes};
+    use quote::quote;
+    use syn::{FieldsNamed, TypePath};
+
+    #[test]
+    pub fn supported_types() {
+        assert!(is_supported("u128"));
+        assert!(is_supported("ScVal"));
+        assert!(is_supported("Value"));
+        assert!(!is_supported("HashMap"));
+        assert!(is_supported("bool"));
+    }
+
+    #[test]
+    pub fn option_fields() {
+        let optional: TypePath = syn::parse_str("Option<i64>").unwrap();
+        assert_eq!(option_inner_type(&optional).unwrap().to_string(), "i64");
+
+        let qualified: TypePath = syn::parse_str("Option<serde_json::Value>").unwrap();
+        assert_eq!(option_inner_type(&qualified).unwrap().to_string(), "Value");
+
+        let required: TypePath = syn::parse_str("i64").unwrap();
+        assert!(option_inner_type(&required).is_none());
+    }
+
+    #[test]
+    pub fn field_attribute_parsing() {
+        let fields: FieldsNamed = syn::parse_str(
+            r#"{
+                #[column_name = "idx"]
+                index: i64,
+                #[zephyr(rename = "val", skip)]
+                value: i64,
+                #[skip]
+                cache: i64,
+                plain: i64,
+                #[zephyr(primary_key, index)]
+                key: i64,
+            }"#,
+        )
+        .unwrap();
+        let attrs: Vec<_> = fields.named.iter().map(|field| field_attributes(field).unwrap()).collect();
+
+        assert_eq!(attrs[0], FieldAttributes { rename: Some("idx".to_string()), ..Default::default() });
+        assert_eq!(attrs[1], FieldAttributes { rename: Some("val".to_string()), skip: true, ..Default::default() });
+        assert_eq!(attrs[2], FieldAttributes { skip: true, ..Default::default() });
+        assert_eq!(attrs[3], FieldAttributes::default());
+        assert_eq!(attrs[4], FieldAttributes { primary_key: true, indexed: true, ..Default::default() });
+    }
+
+    #[test]
+    pub fn zephyr_val_accessors() {
+        let accessor = |field_type| zephyr_val_accessor(field_type).to_string();
+
+        assert_eq!(accessor("i64"), quote! { try_into_i64() }.to_string());
+        assert_eq!(accessor("u32"), quote! { try_into_u32() }.to_string());
+        assert_eq!(accessor("f64"), quote! { try_as_f64() }.to_string());
+        assert_eq!(accessor("bool"), quote! { try_as_bool() }.to_string());
+        assert_eq!(accessor("String"), quote! { try_as_string().map(str::to_string) }.to_string());
+    }
+}
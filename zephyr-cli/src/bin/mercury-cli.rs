@@ -4,14 +4,29 @@ use std::{
 };
 
 use clap::Parser;
-use mercury_cli::{Cli, Commands, MercuryClient, ZephyrProjectParser};
+use mercury_cli::{
+    load_dotenv, parse_function_args, programs_table, Cli, Commands, Config, MercuryClient,
+    ZephyrProjectParser,
+};
 
 const BACKEND_ENDPOINT: &str = "https://api.mercurydata.app";
 const MAINNET_BACKEND_ENDPOINT: &str = "https://mainnet.mercurydata.app";
 const LOCAL_BACKEND: &str = "http://127.0.0.1:8443";
 
+/// Prints the issues found by the local checks of the configuration and
+/// exits if there are any.
+fn exit_on_invalid_config(parser: &ZephyrProjectParser) {
+    if let Err(issues) = parser.validate_local() {
+        for issue in issues {
+            println!("[-] {}", issue);
+        }
+        std::process::exit(1);
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    load_dotenv(".env");
     let cli = Cli::parse();
 
     let client = if let Some(true) = cli.local {
@@ -43,6 +58,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 println!("Parsing project configuration ...");
                 let parser = ZephyrProjectParser::from_path(client, "./zephyr.toml").unwrap();
+                exit_on_invalid_config(&parser);
                 println!("Building binary ...");
                 parser.build_wasm().unwrap();
                 println!("Deploying tables ...");
@@ -179,6 +195,77 @@ lto = true
             )?;
         }
 
+        Some(Commands::List) => match client.list_programs().await {
+            Ok(programs) => print!("{}", programs_table(&programs)),
+            Err(error) => println!("[-] Failed to list programs: {}", error),
+        },
+
+        Some(Commands::Delete { name }) => {
+            let name = name.unwrap_or_else(|| Config::from_path("./zephyr.toml").unwrap().name);
+
+            match client.delete_program(&name).await {
+                Ok(()) => println!("[+] Program \"{}\" deleted successfully", name),
+                Err(error) => println!("[-] Failed to delete program \"{}\": {}", name, error),
+            }
+        }
+
+        Some(Commands::Logs { name, tail }) => {
+            let name = name.unwrap_or_else(|| Config::from_path("./zephyr.toml").unwrap().name);
+
+            match client.get_program_logs(&name, tail.unwrap_or(50)).await {
+                Ok(logs) => {
+                    for log in logs {
+                        println!("{}", log.pretty())
+                    }
+                }
+                Err(error) => println!("[-] Failed to fetch logs of \"{}\": {}", name, error),
+            }
+        }
+
+        Some(Commands::Validate) => {
+            let parser = ZephyrProjectParser::from_path(client, "./zephyr.toml").unwrap();
+            println!("Validating project configuration ...");
+            exit_on_invalid_config(&parser);
+            if let Some(report) = parser.optimized_size_report() {
+                println!("{}", report);
+            }
+
+            match parser.validate().await {
+                Ok(issues) if issues.is_empty() => println!("[+] Configuration is valid."),
+                Ok(issues) => {
+                    for issue in issues {
+                        println!("[-] {}", issue);
+                    }
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("[-] Validation failed: {}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Some(Commands::Schema) => {
+            let config = Config::from_path("./zephyr.toml").unwrap();
+            print!("{}", config.schema_sql());
+        }
+
+        #[cfg(feature = "watch")]
+        Some(Commands::Watch) => {
+            let parser = ZephyrProjectParser::from_path(client, "./zephyr.toml").unwrap();
+            mercury_cli::watch(&parser).await?;
+        }
+
+        Some(Commands::FunctionInvoke { function, args }) => {
+            match parse_function_args(args.as_deref()) {
+                Ok(args) => match client.invoke_function(&function, args).await {
+                    Ok(result) => println!("{}", serde_json::to_string_pretty(&result)?),
+                    Err(error) => println!("[-] Failed to invoke \"{}\": {}", function, error),
+                },
+                Err(error) => println!("[-] {}", error),
+            }
+        }
+
         None => {
             println!("Usage: zephyr deploy")
         }
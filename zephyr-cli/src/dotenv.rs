@@ -0,0 +1,62 @@
+use std::path::Path;
+
+/// Parses `VARNAME=value` lines, skipping empty lines and `#` comments.
+/// Values can be wrapped in single or double quotes.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Sets the variables of a `.env` file that aren't already set in the
+/// environment. Does nothing if the file doesn't exist.
+pub fn load_dotenv<P: AsRef<Path>>(path: P) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+
+    for (key, value) in parse_dotenv(&content) {
+        if std::env::var_os(&key).is_none() {
+            std::env::set_var(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_dotenv;
+
+    #[test]
+    pub fn dotenv_lines() {
+        let content = r#"
+# credentials
+MERCURY_JWT="ey.jwt"
+export OTHER = 'quoted value'
+INVALID
+EMPTY=
+"#;
+
+        assert_eq!(
+            parse_dotenv(content),
+            vec![
+                ("MERCURY_JWT".to_string(), "ey.jwt".to_string()),
+                ("OTHER".to_string(), "quoted value".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+}
@@ -10,4 +10,16 @@ pub enum ParserError {
 
     #[error("Error when compiling program: {0}.")]
     WasmBuildError(String),
+
+    #[error("Error when reading compiled program: {0}.")]
+    WasmReadError(String),
+
+    #[error("Compiled program is {size} bytes, exceeding the {limit} bytes limit.")]
+    WasmTooLarge { size: usize, limit: usize },
+}
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Request failed with status code {status}: {body}")]
+    RequestFailed { status: u16, body: String },
 }
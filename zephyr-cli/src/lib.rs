@@ -5,15 +5,28 @@ use std::io::Read;
 
 use clap::{Parser, Subcommand};
 
+mod dotenv;
 mod error;
+mod logs;
 mod parser;
-
-pub use parser::ZephyrProjectParser;
+mod retry;
+#[cfg(feature = "watch")]
+mod watch;
+
+pub use dotenv::load_dotenv;
+pub use error::ClientError;
+pub use logs::{LogEntry, LogLevel};
+pub use parser::{Config, ValidationIssue, ZephyrProjectParser};
+pub use retry::{retry_with_backoff, BackoffConfig, Transient};
+#[cfg(feature = "watch")]
+pub use watch::watch;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    #[arg(short, long)]
+    /// JWT authenticating the requests, read from `MERCURY_JWT` (also in
+    /// a `.env` file) when not provided.
+    #[arg(short, long, env = "MERCURY_JWT")]
     pub jwt: Option<String>,
 
     #[arg(short, long)]
@@ -65,6 +78,46 @@ pub enum Commands {
         #[arg(short, long)]
         name: String,
     },
+
+    /// Lists the deployed programs.
+    List,
+
+    /// Deletes a deployed program, by default the one in `zephyr.toml`.
+    Delete {
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+
+    /// Prints the most recent logs of a program, by default the one in
+    /// `zephyr.toml`.
+    Logs {
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Number of logs to print, 0 prints all of them.
+        #[arg(short, long)]
+        tail: Option<usize>,
+    },
+
+    /// Checks the tables in `zephyr.toml` locally and against the server.
+    Validate,
+
+    /// Prints the SQL creating the tables in `zephyr.toml`.
+    Schema,
+
+    /// Rebuilds and redeploys the program when its sources change.
+    #[cfg(feature = "watch")]
+    Watch,
+
+    /// Invokes a serverless function of the deployed program.
+    FunctionInvoke {
+        #[arg(short, long)]
+        function: String,
+
+        /// JSON arguments of the function, `{}` by default.
+        #[arg(short, long)]
+        args: Option<String>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -79,14 +132,99 @@ struct CodeUploadClient {
     force_replace: Option<bool>,
 }
 
+/// A program deployed on Mercury.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProgramInfo {
+    pub name: String,
+    pub deployed_at: String,
+
+    /// Size of the program's binary in bytes.
+    pub wasm_size: u64,
+}
+
 pub struct MercuryClient {
     pub base_url: String,
     pub jwt: String,
+    pub backoff: BackoffConfig,
+
+    /// HTTP client shared by all requests, so that connections are pooled.
+    client: reqwest::Client,
 }
 
 impl MercuryClient {
     pub fn new(base_url: String, jwt: String) -> Self {
-        Self { base_url, jwt }
+        Self {
+            base_url,
+            jwt,
+            backoff: BackoffConfig::default(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Sets the timeout of each request, including the time to connect
+    /// and read the response.
+    pub fn with_timeout(mut self, duration: std::time::Duration) -> Self {
+        self.client = reqwest::Client::builder()
+            .timeout(duration)
+            .build()
+            .expect("the HTTP client only fails to build when TLS can't be initialized");
+        self
+    }
+
+    /// Posts `body` to `url`. Server errors are only retried when the
+    /// request is `idempotent`, see [`retry_with_backoff`].
+    async fn post_json(
+        &self,
+        url: String,
+        body: String,
+        idempotent: bool,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let authorization = format!("Bearer {}", &self.jwt);
+
+        retry_with_backoff(&self.backoff, idempotent, || {
+            self.client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", &authorization)
+                .body(body.clone())
+                .send()
+        })
+        .await
+    }
+
+    /// Sends a request without body to `url`, with the URL-encoded `query`
+    /// parameters.
+    async fn send_without_body(
+        &self,
+        method: reqwest::Method,
+        url: String,
+        query: &[(&str, String)],
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let authorization = format!("Bearer {}", &self.jwt);
+
+        retry_with_backoff(&self.backoff, method.is_idempotent(), || {
+            self.client
+                .request(method.clone(), &url)
+                .query(query)
+                .header("Content-Type", "application/json")
+                .header("Authorization", &authorization)
+                .send()
+        })
+        .await
+    }
+
+    /// Returns an error with the response's body when the request failed.
+    async fn error_for_status(
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, ClientError> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        Err(ClientError::RequestFailed {
+            status: response.status().as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        })
     }
 
     pub async fn new_table(&self, table: Table) -> Result<(), Box<dyn std::error::Error>> {
@@ -97,6 +235,8 @@ impl MercuryClient {
             cols.push(Column {
                 name: col.name.to_string(),
                 col_type: col.col_type.to_string(),
+                primary: col.primary,
+                index: col.index,
             });
         }
 
@@ -107,23 +247,13 @@ impl MercuryClient {
 
         let json_code = serde_json::to_string(&code)?;
         let url = format!("{}/zephyr_table_new", &self.base_url);
-        let authorization = format!("Bearer {}", &self.jwt);
-
-        let client = reqwest::Client::new();
 
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .header("Authorization", authorization)
-            .body(json_code)
-            .send()
-            .await
-            .unwrap();
+        let response = self.post_json(url, json_code, false).await?;
 
         if response.status().is_success() {
             println!(
                 "[+] Table \"{}\" created successfully",
-                response.text().await.unwrap()
+                response.text().await?
             );
         } else {
             println!(
@@ -154,18 +284,8 @@ impl MercuryClient {
         let json_code = serde_json::to_string(&code)?;
 
         let url = format!("{}/zephyr_upload", &self.base_url);
-        let authorization = format!("Bearer {}", &self.jwt);
-
-        let client = reqwest::Client::new();
 
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .header("Authorization", authorization)
-            .body(json_code)
-            .send()
-            .await
-            .unwrap();
+        let response = self.post_json(url, json_code, false).await?;
 
         if response.status().is_success() {
             println!("[+] Deployed was successful!");
@@ -217,33 +337,110 @@ impl MercuryClient {
         Ok(())
     }
 
-    async fn catchup(&self, request: CatchupRequest) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn list_programs(&self) -> Result<Vec<ProgramInfo>, Box<dyn std::error::Error>> {
+        let url = format!("{}/zephyr/programs", &self.base_url);
+
+        let response = self
+            .send_without_body(reqwest::Method::GET, url, &[])
+            .await?;
+        let response = Self::error_for_status(response).await?;
+
+        Ok(response.json().await?)
+    }
+
+    fn program_url(&self, name: &str) -> String {
+        format!("{}/zephyr/programs/{}", &self.base_url, name)
+    }
+
+    pub async fn delete_program(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let url = self.program_url(name);
+
+        let response = self
+            .send_without_body(reqwest::Method::DELETE, url, &[])
+            .await?;
+        Self::error_for_status(response).await?;
+
+        Ok(())
+    }
+
+    /// Returns the last `tail` logs of the program, or all of them when
+    /// `tail` is 0.
+    pub async fn get_program_logs(
+        &self,
+        name: &str,
+        tail: usize,
+    ) -> Result<Vec<LogEntry>, Box<dyn std::error::Error>> {
+        let url = format!("{}/zephyr/logs", &self.base_url);
+        let mut query = vec![("name", name.to_string())];
+        if tail > 0 {
+            query.push(("tail", tail.to_string()));
+        }
+
+        let response = self
+            .send_without_body(reqwest::Method::GET, url, &query)
+            .await?;
+        let response = Self::error_for_status(response).await?;
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn invoke_function(
+        &self,
+        function: &str,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        let request = CatchupRequest {
+            mode: ExecutionMode::Function(InvokeZephyrFunction {
+                fname: function.to_string(),
+                arguments: args.to_string(),
+            }),
+        };
         let json_code = serde_json::to_string(&request)?;
 
         let url = format!("{}/zephyr/execute", &self.base_url);
-        let authorization = format!("Bearer {}", &self.jwt);
 
-        let client = reqwest::Client::new();
+        let response = self.post_json(url, json_code, false).await?;
+        let response = Self::error_for_status(response).await?;
 
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .header("Authorization", authorization)
-            .body(json_code)
-            .send()
-            .await
-            .unwrap();
+        Ok(response.json().await?)
+    }
+
+    /// Runs the local checks of [`Config::validate`], then asks the server
+    /// to validate the configuration (e.g. the column types and whether the
+    /// project name is taken). Returns all the issues found.
+    pub async fn validate_config(
+        &self,
+        config: &Config,
+    ) -> Result<Vec<ValidationIssue>, Box<dyn std::error::Error>> {
+        let mut issues = config.validate();
+
+        let json_code = serde_json::to_string(config)?;
+        let url = format!("{}/zephyr/validate", &self.base_url);
+
+        let response = self.post_json(url, json_code, true).await?;
+        let response = Self::error_for_status(response).await?;
+        issues.extend(response.json::<Vec<ValidationIssue>>().await?);
+
+        Ok(issues)
+    }
+
+    async fn catchup(&self, request: CatchupRequest) -> Result<(), Box<dyn std::error::Error>> {
+        let json_code = serde_json::to_string(&request)?;
+
+        let url = format!("{}/zephyr/execute", &self.base_url);
+
+        let response = self.post_json(url, json_code, false).await?;
 
         if response.status().is_success() {
             println!(
                 "Catchup request sent successfully: {}",
-                response.text().await.unwrap()
+                response.text().await?
             )
         } else {
             println!(
                 "[-] Request failed with status code: {:?}, {}",
                 response.status(),
-                response.text().await.unwrap()
+                response.text().await?
             );
         };
 
@@ -251,6 +448,50 @@ impl MercuryClient {
     }
 }
 
+/// Formats the programs as a table, one program per row.
+pub fn programs_table(programs: &[ProgramInfo]) -> String {
+    let header = ("NAME", "DEPLOYED", "SIZE (BYTES)");
+
+    let name_width = programs
+        .iter()
+        .map(|p| p.name.len())
+        .chain([header.0.len()])
+        .max()
+        .unwrap_or_default();
+    let date_width = programs
+        .iter()
+        .map(|p| p.deployed_at.len())
+        .chain([header.1.len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "{:name_width$}  {:date_width$}  {}\n",
+        header.0, header.1, header.2
+    );
+    for program in programs {
+        table.push_str(&format!(
+            "{:name_width$}  {:date_width$}  {}\n",
+            program.name, program.deployed_at, program.wasm_size
+        ));
+    }
+
+    table
+}
+
+/// Parses the `--args` of a function invocation, defaulting to `{}`.
+pub fn parse_function_args(args: Option<&str>) -> Result<serde_json::Value, String> {
+    match args {
+        Some(args) => serde_json::from_str(args).map_err(|e| {
+            format!(
+                "--args must be valid JSON ({}), e.g. '{{\"amount\": 10}}'",
+                e
+            )
+        }),
+        None => Ok(serde_json::json!({})),
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct InvokeZephyrFunction {
     fname: String,
@@ -278,3 +519,170 @@ pub enum ExecutionMode {
 pub struct CatchupRequest {
     mode: ExecutionMode,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parse_function_args, programs_table, Cli, MercuryClient, ProgramInfo};
+    use crate::ClientError;
+    use clap::{CommandFactory, Parser};
+    use mockito::Matcher;
+
+    #[test]
+    pub fn jwt_from_env() {
+        // Checks the wiring instead of setting `MERCURY_JWT`, which would
+        // leak into the tests running concurrently.
+        let command = Cli::command();
+        let jwt = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == "jwt")
+            .unwrap();
+        assert_eq!(jwt.get_env(), Some(std::ffi::OsStr::new("MERCURY_JWT")));
+
+        let cli = Cli::try_parse_from(["mercury-cli", "--jwt", "from-flag", "list"]).unwrap();
+        assert_eq!(cli.jwt.as_deref(), Some("from-flag"));
+    }
+
+    #[test]
+    pub fn function_args() {
+        assert_eq!(parse_function_args(None), Ok(serde_json::json!({})));
+        assert_eq!(
+            parse_function_args(Some(r#"{"address": "CA..", "amount": 10}"#)),
+            Ok(serde_json::json!({"address": "CA..", "amount": 10}))
+        );
+        assert!(parse_function_args(Some("{address: 1}"))
+            .unwrap_err()
+            .starts_with("--args must be valid JSON"));
+    }
+
+    #[test]
+    pub fn program_url() {
+        let client = MercuryClient::new("http://127.0.0.1:8443".into(), String::new());
+
+        assert_eq!(
+            client.program_url("op-ratio"),
+            "http://127.0.0.1:8443/zephyr/programs/op-ratio"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_programs() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/zephyr/programs")
+            .match_header("authorization", "Bearer jwt")
+            .with_body(
+                r#"[{"name": "op-ratio", "deployed_at": "2024-05-01T10:00:00Z", "wasm_size": 48213}]"#,
+            )
+            .create_async()
+            .await;
+
+        let client = MercuryClient::new(server.url(), "jwt".into());
+        let programs = client.list_programs().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(programs.len(), 1);
+        assert_eq!(programs[0].name, "op-ratio");
+    }
+
+    #[tokio::test]
+    async fn delete_program() {
+        let mut server = mockito::Server::new_async().await;
+        let deleted = server
+            .mock("DELETE", "/zephyr/programs/op-ratio")
+            .match_header("authorization", "Bearer jwt")
+            .create_async()
+            .await;
+        let missing = server
+            .mock("DELETE", "/zephyr/programs/unknown")
+            .with_status(404)
+            .with_body("program not found")
+            .create_async()
+            .await;
+
+        let client = MercuryClient::new(server.url(), "jwt".into());
+        client.delete_program("op-ratio").await.unwrap();
+
+        let error = client.delete_program("unknown").await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ClientError>(),
+            Some(ClientError::RequestFailed { status: 404, body }) if body == "program not found"
+        ));
+
+        deleted.assert_async().await;
+        missing.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn get_program_logs() {
+        let mut server = mockito::Server::new_async().await;
+        let tailed = server
+            .mock("GET", "/zephyr/logs")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("name".into(), "op ratio&v2".into()),
+                Matcher::UrlEncoded("tail".into(), "50".into()),
+            ]))
+            .match_header("authorization", "Bearer jwt")
+            .with_body(
+                r#"[{"timestamp": "2024-05-01T10:00:00Z", "level": "Info", "message": "hi"}]"#,
+            )
+            .create_async()
+            .await;
+        let all = server
+            .mock("GET", "/zephyr/logs")
+            .match_query(Matcher::Exact("name=op-ratio".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let client = MercuryClient::new(server.url(), "jwt".into());
+
+        let logs = client.get_program_logs("op ratio&v2", 50).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].message, "hi");
+        let logs = client.get_program_logs("op-ratio", 0).await.unwrap();
+        assert!(logs.is_empty());
+
+        tailed.assert_async().await;
+        all.assert_async().await;
+    }
+
+    #[test]
+    pub fn program_info_json() {
+        let programs: Vec<ProgramInfo> = serde_json::from_str(
+            r#"[{"name": "op-ratio", "deployed_at": "2024-05-01T10:00:00Z", "wasm_size": 48213}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            programs,
+            vec![ProgramInfo {
+                name: "op-ratio".into(),
+                deployed_at: "2024-05-01T10:00:00Z".into(),
+                wasm_size: 48213,
+            }]
+        );
+    }
+
+    #[test]
+    pub fn programs_table_alignment() {
+        let programs = vec![
+            ProgramInfo {
+                name: "op-ratio".into(),
+                deployed_at: "2024-05-01".into(),
+                wasm_size: 48213,
+            },
+            ProgramInfo {
+                name: "a".into(),
+                deployed_at: "2024-05-02".into(),
+                wasm_size: 7,
+            },
+        ];
+
+        assert_eq!(
+            programs_table(&programs),
+            "NAME      DEPLOYED    SIZE (BYTES)\n\
+             op-ratio  2024-05-01  48213\n\
+             a         2024-05-02  7\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Mirrors the log levels of `rs_zephyr_common::log::LogLevel`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Debug,
+    Info,
+}
+
+impl LogLevel {
+    /// ANSI color code the level is printed with.
+    fn color(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "31",
+            LogLevel::Warning => "33",
+            LogLevel::Debug => "36",
+            LogLevel::Info => "32",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warning => "WARN",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+        }
+    }
+}
+
+/// A log emitted by a deployed program, mirrors
+/// `rs_zephyr_common::log::ZephyrLog` along with the time it was emitted.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub message: String,
+
+    #[serde(default)]
+    pub data: Option<Vec<u8>>,
+}
+
+impl LogEntry {
+    /// Formats the log on a single line with a colored level, followed by
+    /// the structured fields as `key=value` pairs.
+    pub fn pretty(&self) -> String {
+        let mut line = format!(
+            "{} \x1b[{}m{:<5}\x1b[0m {}",
+            self.timestamp,
+            self.level.color(),
+            self.level.label(),
+            self.message
+        );
+
+        for (key, value) in self.fields() {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+
+        line
+    }
+
+    /// Key-value pairs the program attached to the log, which are stored
+    /// as a JSON object in the log's data.
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let Some(fields) = self.data.as_deref().and_then(|data| {
+            serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(data).ok()
+        }) else {
+            return Vec::new();
+        };
+
+        fields
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(value) => (key, value),
+                other => (key, other.to_string()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LogEntry, LogLevel};
+
+    #[test]
+    pub fn pretty_log() {
+        let mut log: LogEntry = serde_json::from_str(
+            r#"{"timestamp": "2024-05-01T10:00:00Z", "level": "Warning", "message": "slow ledger"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(log.level, LogLevel::Warning);
+        assert_eq!(log.data, None);
+        assert_eq!(
+            log.pretty(),
+            "2024-05-01T10:00:00Z \x1b[33mWARN \x1b[0m slow ledger"
+        );
+
+        log.data = Some(br#"{"ledger":"2000"}"#.to_vec());
+        assert_eq!(
+            log.pretty(),
+            "2024-05-01T10:00:00Z \x1b[33mWARN \x1b[0m slow ledger ledger=2000"
+        );
+    }
+}
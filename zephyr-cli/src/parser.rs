@@ -1,13 +1,190 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Read, path::Path, process::Command};
+use std::{collections::HashSet, fmt, fs::File, io::Read, path::Path, process::Command};
+
+use rs_zephyr_common::schema::{table_ddl, ColumnSchema, COLUMN_TYPE};
+pub use rs_zephyr_common::schema::{Column, Table};
 
 use crate::{error::ParserError, MercuryClient};
 
+/// Maximum size of a program binary accepted by Mercury.
+pub const MAX_WASM_SIZE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Size above which the build suggests optimizing the program.
+pub const WASM_SIZE_WARNING_BYTES: usize = 1024 * 1024;
+
+/// Formats a size in bytes as KB, or MB from 1 MB.
+fn format_size(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Maximum length of table and column names, which the SDK encodes as
+/// small symbols.
+pub const MAX_SYMBOL_LENGTH: usize = 9;
+
+/// An issue found in a table of the project configuration.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub table: String,
+
+    /// The column the issue refers to, if any.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(
+                f,
+                "table {}, column {}: {}",
+                self.table, column, self.message
+            ),
+            None => write!(f, "table {}: {}", self.table, self.message),
+        }
+    }
+}
+
+/// Runs `wasm-opt -Oz` on a copy of the program and returns the optimized
+/// size, or `None` if `wasm-opt` isn't in `PATH` or fails.
+fn optimized_size(path: &str) -> Option<usize> {
+    // The temporary file is unique and removed when dropped.
+    let output = tempfile::Builder::new().suffix(".wasm").tempfile().ok()?;
+    let status = Command::new("wasm-opt")
+        .args(["-Oz", path, "-o"])
+        .arg(output.path())
+        .output()
+        .ok()?
+        .status;
+
+    if !status.success() {
+        return None;
+    }
+
+    Some(std::fs::metadata(output.path()).ok()?.len() as usize)
+}
+
+/// Returns why `name` can't be used as a symbol, if it can't.
+fn symbol_issue(name: &str) -> Option<String> {
+    if name.is_empty() {
+        Some("name is empty".into())
+    } else if name.len() > MAX_SYMBOL_LENGTH {
+        Some(format!(
+            "name is {} characters long, the maximum is {}",
+            name.len(),
+            MAX_SYMBOL_LENGTH
+        ))
+    } else {
+        name.chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+            .map(|c| format!("name contains {:?}, only [a-zA-Z0-9_] are allowed", c))
+    }
+}
+
 impl Config {
+    /// Reads the project configuration from a `zephyr.toml` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Checks the names of the tables and the names and types of their
+    /// columns without contacting the server.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut tables = HashSet::new();
+
+        for table in self.tables() {
+            if let Some(message) = symbol_issue(&table.name) {
+                issues.push(ValidationIssue {
+                    table: table.name.clone(),
+                    column: None,
+                    message,
+                });
+            }
+
+            if !tables.insert(table.name.clone()) {
+                issues.push(ValidationIssue {
+                    table: table.name.clone(),
+                    column: None,
+                    message: "duplicate table name".into(),
+                });
+            }
+
+            let mut seen = HashSet::new();
+            for column in &table.columns {
+                if let Some(message) = symbol_issue(&column.name) {
+                    issues.push(ValidationIssue {
+                        table: table.name.clone(),
+                        column: Some(column.name.clone()),
+                        message,
+                    });
+                }
+
+                if !seen.insert(&column.name) {
+                    issues.push(ValidationIssue {
+                        table: table.name.clone(),
+                        column: Some(column.name.clone()),
+                        message: "duplicate column name".into(),
+                    });
+                }
+
+                if !column.col_type.eq_ignore_ascii_case(COLUMN_TYPE) {
+                    issues.push(ValidationIssue {
+                        table: table.name.clone(),
+                        column: Some(column.name.clone()),
+                        message: format!(
+                            "unsupported column type {}, programs store every column as {}",
+                            column.col_type, COLUMN_TYPE
+                        ),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Returns the PostgreSQL statements creating the tables and their
+    /// indexes, see [`table_ddl`].
+    pub fn schema_sql(&self) -> String {
+        let mut sql = String::new();
+
+        for table in self.tables() {
+            let columns = table.columns.iter().map(|column| ColumnSchema {
+                name: &column.name,
+                primary_key: column.primary == Some(true),
+                indexed: column.index == Some(true),
+            });
+
+            sql.push_str(&table_ddl(&table.name, columns));
+            sql.push('\n');
+        }
+
+        sql
+    }
+
     fn tables(&self) -> Vec<Table> {
         self.tables.clone().unwrap_or(vec![])
     }
+
+    fn wasm_size_warning(&self) -> usize {
+        self.warn_wasm_kb
+            .map(|kb| kb as usize * 1024)
+            .unwrap_or(WASM_SIZE_WARNING_BYTES)
+    }
+
+    fn max_wasm_size(&self) -> usize {
+        self.max_wasm_kb
+            .map(|kb| kb as usize * 1024)
+            .unwrap_or(MAX_WASM_SIZE_BYTES)
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -16,18 +193,16 @@ pub struct Config {
 
     /// Tables that the poject is writing or reading.
     pub tables: Option<Vec<Table>>,
-}
 
-#[derive(Deserialize, Serialize, Clone)]
-pub struct Table {
-    pub name: String,
-    pub columns: Vec<Column>,
-}
+    /// Overrides the maximum size (in KB) of the compiled program.
+    pub max_wasm_kb: Option<u32>,
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct Column {
-    pub name: String,
-    pub col_type: String,
+    /// Overrides the size (in KB) above which the build suggests running
+    /// `wasm-opt`.
+    pub warn_wasm_kb: Option<u32>,
+
+    /// Overrides the path of the compiled program.
+    pub wasm_path: Option<String>,
 }
 
 pub struct ZephyrProjectParser {
@@ -37,21 +212,37 @@ pub struct ZephyrProjectParser {
 
 impl ZephyrProjectParser {
     pub fn from_path<P: AsRef<Path>>(client: MercuryClient, path: P) -> Result<Self> {
-        let project_definition = {
-            let mut content = String::new();
-            File::open(path)?.read_to_string(&mut content)?;
-
-            content
-        };
-
         let parser = Self {
             client,
-            config: toml::from_str(&project_definition)?,
+            config: Config::from_path(path)?,
         };
 
         Ok(parser)
     }
 
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Checks the configuration without contacting the server, see
+    /// [`Config::validate`].
+    pub fn validate_local(&self) -> Result<(), Vec<String>> {
+        let issues = self.config.validate();
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        Err(issues.iter().map(ToString::to_string).collect())
+    }
+
+    /// Validates the configuration both locally and against the server.
+    pub async fn validate(&self) -> Result<Vec<ValidationIssue>> {
+        self.client
+            .validate_config(&self.config)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))
+    }
+
     pub fn build_wasm(&self) -> Result<()> {
         let output = Command::new("cargo")
             .args(&[
@@ -71,9 +262,43 @@ impl ZephyrProjectParser {
             return Err(ParserError::WasmBuildError(error).into());
         }
 
+        // The report is informative only, a missing binary is caught when deploying.
+        match self.wasm_size_report(&self.wasm_path(None)) {
+            Ok(report) => println!("{}", report),
+            Err(error) => println!("[!] {}", error),
+        }
+
         Ok(())
     }
 
+    /// Describes the size of the compiled program, with a hint to run
+    /// `wasm-opt` when it is above the warning threshold.
+    fn wasm_size_report(&self, path: &str) -> Result<String, ParserError> {
+        let size = std::fs::metadata(path)
+            .map_err(|e| ParserError::WasmReadError(e.to_string()))?
+            .len() as usize;
+
+        let mut report = format!("(Size of program is {})", format_size(size));
+        if size > self.config.wasm_size_warning() {
+            report.push_str(&format!(
+                "\n[!] The program is larger than {}, consider optimizing it with `wasm-opt -Oz`.",
+                format_size(self.config.wasm_size_warning())
+            ));
+        }
+
+        Ok(report)
+    }
+
+    /// Reports the size `wasm-opt -Oz` optimizes the compiled program to,
+    /// or `None` when `wasm-opt` isn't installed or the program isn't built.
+    pub fn optimized_size_report(&self) -> Option<String> {
+        let optimized = optimized_size(&self.wasm_path(None))?;
+        Some(format!(
+            "(Size after `wasm-opt -Oz` is {})",
+            format_size(optimized)
+        ))
+    }
+
     pub async fn deploy_tables(&self) -> Result<()> {
         for table in self.config.tables() {
             if let Err(_) = self.client.new_table(table).await {
@@ -84,16 +309,47 @@ impl ZephyrProjectParser {
         Ok(())
     }
 
-    pub async fn deploy_wasm(&self, target: Option<String>) -> Result<()> {
+    /// Resolves the path of the compiled program. In order of priority:
+    /// the target directory, the `ZEPHYR_WASM_PATH` environment variable,
+    /// the `wasm_path` configuration and the default cargo output path.
+    fn wasm_path(&self, target: Option<String>) -> String {
         let project_name = &self.config.name;
-        let path = if let Some(target_dir) = target {
+        if let Some(target_dir) = target {
             format!("{}/{}.wasm", target_dir, project_name.replace('-', "_"))
+        } else if let Ok(path) = std::env::var("ZEPHYR_WASM_PATH") {
+            path
+        } else if let Some(path) = &self.config.wasm_path {
+            path.clone()
         } else {
             format!(
                 "./target/wasm32-unknown-unknown/release/{}.wasm",
                 project_name.replace('-', "_")
             )
-        };
+        }
+    }
+
+    /// Checks that the compiled program is within the deploy size limit
+    /// and returns its size in bytes.
+    pub fn check_wasm_size(&self) -> Result<usize, ParserError> {
+        self.check_wasm_size_at(&self.wasm_path(None))
+    }
+
+    fn check_wasm_size_at(&self, path: &str) -> Result<usize, ParserError> {
+        let size = std::fs::metadata(path)
+            .map_err(|e| ParserError::WasmReadError(e.to_string()))?
+            .len() as usize;
+        let limit = self.config.max_wasm_size();
+
+        if size > limit {
+            return Err(ParserError::WasmTooLarge { size, limit });
+        }
+
+        Ok(size)
+    }
+
+    pub async fn deploy_wasm(&self, target: Option<String>) -> Result<()> {
+        let path = self.wasm_path(target);
+        self.check_wasm_size_at(&path)?;
 
         if let Err(_) = self.client.deploy(path, true).await {
             return Err(ParserError::WasmDeploymentError.into());
@@ -105,7 +361,8 @@ impl ZephyrProjectParser {
 
 #[cfg(test)]
 mod test {
-    use super::{Column, Config, Table};
+    use super::{Column, Config, Table, ValidationIssue, ZephyrProjectParser};
+    use crate::{error::ParserError, MercuryClient};
 
     #[test]
     pub fn sample_config() {
@@ -117,15 +374,198 @@ mod test {
                     Column {
                         name: "soroban".into(),
                         col_type: "BYTEA".into(), // only supported type as of now
+                        primary: None,
+                        index: None,
                     },
                     Column {
                         name: "ratio".into(),
                         col_type: "BYTEA".into(), // only supported type as of now
+                        primary: None,
+                        index: None,
                     },
                 ],
             }]),
+            max_wasm_kb: None,
+            warn_wasm_kb: None,
+            wasm_path: None,
         };
 
         println!("{}", toml::to_string(&config).unwrap());
     }
+
+    #[test]
+    pub fn wasm_size_limit() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), vec![0_u8; 2048]).unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let mut parser = ZephyrProjectParser {
+            config: Config {
+                name: "size-limit".into(),
+                tables: None,
+                max_wasm_kb: Some(1),
+                warn_wasm_kb: None,
+                wasm_path: None,
+            },
+            client: MercuryClient::new(String::new(), String::new()),
+        };
+
+        assert!(matches!(
+            parser.check_wasm_size_at(path),
+            Err(ParserError::WasmTooLarge {
+                size: 2048,
+                limit: 1024
+            })
+        ));
+
+        parser.config.max_wasm_kb = Some(2);
+        assert_eq!(parser.check_wasm_size_at(path).unwrap(), 2048);
+    }
+
+    #[test]
+    pub fn configured_wasm_path() {
+        let parser = ZephyrProjectParser {
+            config: toml::from_str(
+                r#"
+name = "my-program"
+wasm_path = "custom/path/my.wasm"
+"#,
+            )
+            .unwrap(),
+            client: MercuryClient::new(String::new(), String::new()),
+        };
+
+        assert_eq!(parser.wasm_path(None), "custom/path/my.wasm");
+        assert_eq!(parser.wasm_path(Some("out".into())), "out/my_program.wasm");
+    }
+
+    #[test]
+    pub fn local_validation() {
+        let config: Config = toml::from_str(
+            r#"
+name = "my-program"
+
+[[tables]]
+name = "balances_by_account"
+columns = [
+    { name = "account", col_type = "BYTEA" },
+    { name = "balance", col_type = "BYTEA" },
+    { name = "account", col_type = "BYTEA" },
+    { name = "last-seen", col_type = "BYTEA" },
+]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(),
+            vec![
+                ValidationIssue {
+                    table: "balances_by_account".into(),
+                    column: None,
+                    message: "name is 19 characters long, the maximum is 9".into(),
+                },
+                ValidationIssue {
+                    table: "balances_by_account".into(),
+                    column: Some("account".into()),
+                    message: "duplicate column name".into(),
+                },
+                ValidationIssue {
+                    table: "balances_by_account".into(),
+                    column: Some("last-seen".into()),
+                    message: "name contains '-', only [a-zA-Z0-9_] are allowed".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn wasm_size_report() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), vec![0_u8; 3 * 1024]).unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let mut parser = ZephyrProjectParser {
+            config: toml::from_str(r#"name = "size-report""#).unwrap(),
+            client: MercuryClient::new(String::new(), String::new()),
+        };
+
+        let report = parser.wasm_size_report(path).unwrap();
+        assert!(report.starts_with("(Size of program is 3.00 KB)"));
+        assert!(!report.contains("wasm-opt -Oz`."));
+
+        parser.config.warn_wasm_kb = Some(2);
+        let report = parser.wasm_size_report(path).unwrap();
+        assert!(report.contains(
+            "[!] The program is larger than 2.00 KB, consider optimizing it with `wasm-opt -Oz`."
+        ));
+    }
+
+    #[test]
+    pub fn validate_local() {
+        let parser = ZephyrProjectParser {
+            config: toml::from_str(
+                r#"
+name = "my-program"
+
+[[tables]]
+name = "ledgers"
+columns = [
+    { name = "sequence", col_type = "bytea" },
+    { name = "hash", col_type = "BLOB" },
+]
+
+[[tables]]
+name = "ledgers"
+columns = [{ name = "sequence", col_type = "BIGINT" }]
+"#,
+            )
+            .unwrap(),
+            client: MercuryClient::new(String::new(), String::new()),
+        };
+
+        assert_eq!(
+            parser.validate_local(),
+            Err(vec![
+                "table ledgers, column hash: unsupported column type BLOB, programs store every column as BYTEA".to_string(),
+                "table ledgers: duplicate table name".to_string(),
+                "table ledgers, column sequence: unsupported column type BIGINT, programs store every column as BYTEA".to_string(),
+            ])
+        );
+
+        let parser = ZephyrProjectParser {
+            config: toml::from_str(r#"name = "no-tables""#).unwrap(),
+            client: MercuryClient::new(String::new(), String::new()),
+        };
+        assert_eq!(parser.validate_local(), Ok(()));
+    }
+
+    #[test]
+    pub fn schema_sql() {
+        let config: Config = toml::from_str(
+            r#"
+name = "my-program"
+
+[[tables]]
+name = "balances"
+columns = [
+    { name = "account", col_type = "BYTEA", primary = true },
+    { name = "asset", col_type = "BYTEA", index = true },
+    { name = "balance", col_type = "BYTEA" },
+]
+
+[[tables]]
+name = "ledgers"
+columns = [{ name = "sequence", col_type = "BYTEA" }]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.schema_sql(),
+            "CREATE TABLE IF NOT EXISTS balances (account BYTEA PRIMARY KEY, asset BYTEA, balance BYTEA);\n\
+             CREATE INDEX IF NOT EXISTS balances_asset_idx ON balances (asset);\n\
+             CREATE TABLE IF NOT EXISTS ledgers (sequence BYTEA);\n"
+        );
+    }
 }
@@ -0,0 +1,173 @@
+use std::{future::Future, time::Duration};
+
+/// Retry policy for requests sent to the Mercury backend.
+#[derive(Clone, Debug)]
+pub struct BackoffConfig {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u8,
+
+    /// Delay before the first retry.
+    pub initial_delay_ms: u64,
+
+    /// Factor the delay is multiplied by after each retry.
+    pub backoff_factor: f64,
+}
+
+impl Default for BackoffConfig {
+    /// Retries up to three times, waiting 1s, 2s and 4s.
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_delay_ms: 1000,
+            backoff_factor: 2.0,
+        }
+    }
+}
+
+/// Outcomes that are worth retrying.
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+}
+
+impl Transient for reqwest::Response {
+    fn is_transient(&self) -> bool {
+        self.status().is_server_error()
+    }
+}
+
+impl Transient for reqwest::Error {
+    /// Only connection failures are transient: the request never reached
+    /// the server, so sending it again can't apply it twice.
+    fn is_transient(&self) -> bool {
+        self.is_connect()
+    }
+}
+
+/// Runs `operation` until it returns a non-transient outcome or the
+/// attempts are exhausted, sleeping with exponential backoff in between.
+///
+/// Transient errors are always retried, while transient responses are
+/// only retried when the request is `idempotent`, since the server may
+/// have applied it before failing. The last outcome is returned.
+pub async fn retry_with_backoff<F, Fut, T, E>(
+    backoff: &BackoffConfig,
+    idempotent: bool,
+    mut operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    T: Transient,
+    E: Transient,
+{
+    let mut delay = backoff.initial_delay_ms as f64;
+    let mut attempt = 1;
+
+    loop {
+        let result = operation().await;
+
+        let transient = match &result {
+            Ok(outcome) => idempotent && outcome.is_transient(),
+            Err(error) => error.is_transient(),
+        };
+
+        if !transient || attempt >= backoff.max_attempts {
+            return result;
+        }
+
+        tokio::time::sleep(Duration::from_millis(delay as u64)).await;
+        delay *= backoff.backoff_factor;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{retry_with_backoff, BackoffConfig, Transient};
+    use std::cell::RefCell;
+
+    struct MockResponse(u16);
+
+    impl Transient for MockResponse {
+        fn is_transient(&self) -> bool {
+            self.0 >= 500
+        }
+    }
+
+    /// Whether the request reached the server.
+    #[derive(Debug)]
+    struct MockError(bool);
+
+    impl Transient for MockError {
+        fn is_transient(&self) -> bool {
+            !self.0
+        }
+    }
+
+    fn instant_backoff() -> BackoffConfig {
+        BackoffConfig {
+            max_attempts: 4,
+            initial_delay_ms: 0,
+            backoff_factor: 2.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let statuses = RefCell::new(vec![200, 503]);
+
+        let response = retry_with_backoff(&instant_backoff(), true, || async {
+            Ok::<_, MockError>(MockResponse(statuses.borrow_mut().pop().unwrap()))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.0, 200);
+        assert!(statuses.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let attempts = RefCell::new(0);
+
+        let response = retry_with_backoff(&instant_backoff(), true, || async {
+            *attempts.borrow_mut() += 1;
+            Ok::<_, MockError>(MockResponse(503))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.0, 503);
+        assert_eq!(*attempts.borrow(), 4);
+    }
+
+    #[tokio::test]
+    async fn keeps_non_idempotent_responses() {
+        let attempts = RefCell::new(0);
+
+        let response = retry_with_backoff(&instant_backoff(), false, || async {
+            *attempts.borrow_mut() += 1;
+            Ok::<_, MockError>(MockResponse(503))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.0, 503);
+        assert_eq!(*attempts.borrow(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_only() {
+        let errors = RefCell::new(vec![MockError(true), MockError(false)]);
+
+        let error = retry_with_backoff(&instant_backoff(), false, || async {
+            Err::<MockResponse, _>(errors.borrow_mut().pop().unwrap())
+        })
+        .await
+        .err()
+        .unwrap();
+
+        assert!(error.0);
+        assert!(errors.borrow().is_empty());
+    }
+}
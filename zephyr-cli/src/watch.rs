@@ -0,0 +1,64 @@
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc::unbounded_channel, task::block_in_place, time::timeout};
+
+use crate::ZephyrProjectParser;
+
+/// Changes closer than this are handled with a single rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Rebuilds the program and redeploys its tables and binary whenever a
+/// file in `src` changes. Failed builds and deployments are printed and
+/// watching continues.
+pub async fn watch(parser: &ZephyrProjectParser) -> Result<()> {
+    // The watcher's callback runs on its own thread, events are forwarded
+    // to a tokio channel so that waiting for them doesn't block the runtime.
+    let (tx, mut rx) = unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let _ = tx.send(event);
+    })?;
+    watcher.watch(Path::new("src"), RecursiveMode::Recursive)?;
+
+    println!("Watching src/ for changes ...");
+
+    // Waits for a change, then for the changes to settle.
+    while let Some(event) = rx.recv().await {
+        event?;
+        loop {
+            match timeout(DEBOUNCE, rx.recv()).await {
+                Ok(Some(event)) => {
+                    event?;
+                }
+                Ok(None) => return Ok(()),
+                Err(_) => break,
+            }
+        }
+
+        println!("Building binary ...");
+        let start = Instant::now();
+        if let Err(error) = block_in_place(|| parser.build_wasm()) {
+            println!("[-] Build failed: {}", error);
+            continue;
+        }
+        println!("Built in {:.2?}", start.elapsed());
+
+        println!("Deploying tables ...");
+        if let Err(error) = parser.deploy_tables().await {
+            println!("[-] Table deployment failed: {}", error);
+            continue;
+        }
+
+        println!("Deploying wasm ...");
+        let start = Instant::now();
+        match parser.deploy_wasm(None).await {
+            Ok(()) => println!("Deployed in {:.2?}", start.elapsed()),
+            Err(error) => println!("[-] Deployment failed: {}", error),
+        }
+    }
+
+    Ok(())
+}
@@ -1,18 +1,293 @@
-use serde::{Deserialize, Serialize};
+use core::{fmt, str::FromStr};
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use thiserror::Error;
 
 /// A generic request object meant to be easily reusable by any HTTP client
 /// request.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+///
+/// `timeout_ms` and `retry` are serialized after the other fields, so hosts
+/// that don't know them ignore them as trailing bytes.
+#[derive(Clone, Serialize, Debug, PartialEq)]
 pub struct AgnosticRequest {
     pub body: Option<String>,
     pub url: String,
     pub method: Method,
     pub headers: Vec<(String, String)>,
+
+    /// Timeout of each attempt, the host's default is used when unset.
+    pub timeout_ms: Option<u64>,
+
+    /// Number of times the host retries a failed request.
+    pub retry: Option<u32>,
+}
+
+/// Fields of [`AgnosticRequest`], for the formats that name them.
+#[derive(Deserialize)]
+struct AgnosticRequestFields {
+    body: Option<String>,
+    url: String,
+    method: Method,
+    headers: Vec<(String, String)>,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    #[serde(default)]
+    retry: Option<u32>,
+}
+
+struct AgnosticRequestVisitor;
+
+impl<'de> Visitor<'de> for AgnosticRequestVisitor {
+    type Value = AgnosticRequest;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct AgnosticRequest")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let missing = |index| de::Error::invalid_length(index, &self);
+
+        let body = seq.next_element()?.ok_or_else(|| missing(0))?;
+        let url = seq.next_element()?.ok_or_else(|| missing(1))?;
+        let method = seq.next_element()?.ok_or_else(|| missing(2))?;
+        let headers = seq.next_element()?.ok_or_else(|| missing(3))?;
+
+        // Requests serialized by previous SDKs end here. Bincode reports the
+        // end of the input as an error rather than as a missing element.
+        let timeout_ms = seq.next_element().ok().flatten().flatten();
+        let retry = seq.next_element().ok().flatten().flatten();
+
+        Ok(AgnosticRequest {
+            body,
+            url,
+            method,
+            headers,
+            timeout_ms,
+            retry,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let fields = AgnosticRequestFields::deserialize(MapAccessDeserializer::new(map))?;
+
+        Ok(AgnosticRequest {
+            body: fields.body,
+            url: fields.url,
+            method: fields.method,
+            headers: fields.headers,
+            timeout_ms: fields.timeout_ms,
+            retry: fields.retry,
+        })
+    }
+}
+
+/// Also reads requests serialized without `timeout_ms` and `retry`.
+impl<'de> Deserialize<'de> for AgnosticRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const FIELDS: &[&str] = &["body", "url", "method", "headers", "timeout_ms", "retry"];
+
+        deserializer.deserialize_struct("AgnosticRequest", FIELDS, AgnosticRequestVisitor)
+    }
+}
+
+impl AgnosticRequest {
+    /// Returns a builder for a `GET` request with no body and headers.
+    pub fn builder() -> AgnosticRequestBuilder {
+        AgnosticRequestBuilder::default()
+    }
+}
+
+/// Builder for [`AgnosticRequest`].
+#[derive(Clone, Debug)]
+pub struct AgnosticRequestBuilder {
+    request: AgnosticRequest,
 }
 
-/// Methods currently supported are Get and Post.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+impl Default for AgnosticRequestBuilder {
+    fn default() -> Self {
+        Self {
+            request: AgnosticRequest {
+                body: None,
+                url: String::new(),
+                method: Method::Get,
+                headers: Vec::new(),
+                timeout_ms: None,
+                retry: None,
+            },
+        }
+    }
+}
+
+impl AgnosticRequestBuilder {
+    pub fn url(mut self, url: impl ToString) -> Self {
+        self.request.url = url.to_string();
+        self
+    }
+
+    pub fn method(mut self, method: Method) -> Self {
+        self.request.method = method;
+        self
+    }
+
+    pub fn body(mut self, body: impl ToString) -> Self {
+        self.request.body = Some(body.to_string());
+        self
+    }
+
+    /// Adds a header, previously added headers are kept.
+    pub fn header(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.request
+            .headers
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.request.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    pub fn retry(mut self, retry: u32) -> Self {
+        self.request.retry = Some(retry);
+        self
+    }
+
+    pub fn build(self) -> AgnosticRequest {
+        self.request
+    }
+}
+
+/// HTTP methods supported by the host.
+///
+/// Note that responses to `Head` requests have no body.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub enum Method {
     Get,
     Post,
+    Patch,
+    Head,
+    Delete,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unsupported HTTP method {0}")]
+pub struct UnsupportedMethod(pub String);
+
+impl FromStr for Method {
+    type Err = UnsupportedMethod;
+
+    /// Parses a case-insensitive method name, e.g. `"PATCH"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "GET" => Ok(Method::Get),
+            "POST" => Ok(Method::Post),
+            "PATCH" => Ok(Method::Patch),
+            "HEAD" => Ok(Method::Head),
+            "DELETE" => Ok(Method::Delete),
+            _ => Err(UnsupportedMethod(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AgnosticRequest, Method, UnsupportedMethod};
+
+    #[test]
+    pub fn builder_matches_literal() {
+        let built = AgnosticRequest::builder()
+            .url("https://example.com")
+            .method(Method::Post)
+            .body("{}")
+            .header("Content-Type", "application/json")
+            .timeout_ms(5000)
+            .retry(2)
+            .build();
+
+        let literal = AgnosticRequest {
+            body: Some("{}".into()),
+            url: "https://example.com".into(),
+            method: Method::Post,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            timeout_ms: Some(5000),
+            retry: Some(2),
+        };
+
+        assert_eq!(built, literal);
+    }
+
+    #[test]
+    pub fn method_from_str() {
+        assert_eq!("PATCH".parse(), Ok(Method::Patch));
+        assert_eq!("delete".parse(), Ok(Method::Delete));
+        assert_eq!(
+            "TRACE".parse::<Method>(),
+            Err(UnsupportedMethod("TRACE".into()))
+        );
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct PreviousRequest {
+        body: Option<String>,
+        url: String,
+        method: u32,
+        headers: Vec<(String, String)>,
+    }
+
+    #[test]
+    pub fn previous_layout_compatibility() {
+        let previous = PreviousRequest {
+            body: None,
+            url: "https://example.com".into(),
+            method: 1,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+        };
+
+        // Requests of previous SDKs are read without timeout and retry.
+        let request: AgnosticRequest =
+            bincode::deserialize(&bincode::serialize(&previous).unwrap()).unwrap();
+        assert_eq!(
+            request,
+            AgnosticRequest::builder()
+                .url("https://example.com")
+                .method(Method::Post)
+                .header("Content-Type", "application/json")
+                .build()
+        );
+
+        // Previous hosts ignore the trailing timeout and retry.
+        let request = AgnosticRequest::builder()
+            .url("https://example.com")
+            .method(Method::Post)
+            .timeout_ms(5000)
+            .retry(2)
+            .build();
+        let read: PreviousRequest =
+            bincode::deserialize(&bincode::serialize(&request).unwrap()).unwrap();
+        assert_eq!(read.url, "https://example.com");
+        assert_eq!(read.method, 1);
+    }
+
+    #[test]
+    pub fn json_roundtrip() {
+        let request = AgnosticRequest::builder()
+            .url("https://example.com")
+            .timeout_ms(5000)
+            .build();
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AgnosticRequest>(&json).unwrap(),
+            request
+        );
+
+        let previous = r#"{"body":null,"url":"https://example.com","method":"Get","headers":[]}"#;
+        assert_eq!(
+            serde_json::from_str::<AgnosticRequest>(previous).unwrap(),
+            AgnosticRequest::builder()
+                .url("https://example.com")
+                .build()
+        );
+    }
 }
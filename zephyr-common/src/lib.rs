@@ -4,6 +4,7 @@
 
 pub mod http;
 pub mod log;
+pub mod schema;
 pub mod wrapping;
 
 pub fn to_fixed<T, const N: usize>(v: Vec<T>) -> [T; N] {
@@ -19,33 +20,49 @@ pub enum ZephyrStatus {
     DbReadError = 3,
     NoValOnStack = 4,
     HostConfiguration = 5,
+    NotFound = 6,
 }
 
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
 use http::AgnosticRequest;
 use log::ZephyrLog;
 use serde::{Deserialize, Serialize};
-use stellar_xdr::next::{LedgerEntry, ScAddress, ScVal};
+use stellar_xdr::next::{AccountId, LedgerEntry, ScAddress, ScVal, TrustLineAsset};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DatabaseError {
-    #[error("Invalid permissions. Tried reading when in write-only")]
+    #[error(
+        "Invalid permissions. Tried reading when in write-only, the program can't read this table."
+    )]
     ReadOnWriteOnly,
 
-    #[error("Invalid permissions. Tried writing when in read-only")]
+    #[error(
+        "Invalid permissions. Tried writing when in read-only, e.g. from a serverless function."
+    )]
     WriteOnReadOnly,
 
-    #[error("Zephyr query malformed.")]
+    #[error(
+        "Zephyr query malformed. Check that the table, columns and conditions are valid symbols."
+    )]
     ZephyrQueryMalformed,
 
-    #[error("Zephyr query error.")]
+    #[error("Zephyr query error. Check that the table and column names exist and match the server schema.")]
     ZephyrQueryError,
 
-    #[error("Unable to write to DB.")]
+    #[error("Unable to write to DB. Check that the values match the columns of the table.")]
     WriteError,
 
-    #[error("Unable to parse operator.")]
+    #[error("Unable to parse operator. Only the conditions supported by the SDK can be used.")]
     OperatorError,
+
+    #[error("Duplicate key. A row with the same unique or primary key already exists, consider updating it instead.")]
+    DuplicateKey,
+
+    #[error("No rows found. The read expected at least one row matching the conditions.")]
+    NotFound,
 }
 
 impl From<anyhow::Error> for ZephyrStatus {
@@ -57,6 +74,8 @@ impl From<anyhow::Error> for ZephyrStatus {
             Some(DatabaseError::ReadOnWriteOnly) => ZephyrStatus::HostConfiguration,
             Some(DatabaseError::WriteOnReadOnly) => ZephyrStatus::HostConfiguration,
             Some(DatabaseError::OperatorError) => ZephyrStatus::DbWriteError, // todo: specific error
+            Some(DatabaseError::DuplicateKey) => ZephyrStatus::DbWriteError,
+            Some(DatabaseError::NotFound) => ZephyrStatus::NotFound,
             None => ZephyrStatus::Unknown,
         }
     }
@@ -71,6 +90,7 @@ impl From<u32> for ZephyrStatus {
             3 => Self::DbReadError,
             4 => Self::NoValOnStack,
             5 => Self::HostConfiguration,
+            6 => Self::NotFound,
             _ => panic!("Unrecoverable status"),
         }
     }
@@ -86,12 +106,342 @@ pub enum ZephyrVal {
     I32(i32),
     F32(f32),
     String(String),
+
+    /// Raw bytes. Types without a dedicated variant are also stored here,
+    /// e.g. `u128` values are stored as their 16 big-endian bytes.
     Bytes(Vec<u8>),
+    Bool(bool),
+    Null,
+    Array(Vec<ZephyrVal>),
+    Map(Vec<(String, ZephyrVal)>),
+}
+
+impl ZephyrVal {
+    /// Position of the variant, used to order values of unrelated types.
+    fn discriminant(&self) -> u8 {
+        match self {
+            ZephyrVal::I128(_) => 0,
+            ZephyrVal::I64(_) => 1,
+            ZephyrVal::U64(_) => 2,
+            ZephyrVal::F64(_) => 3,
+            ZephyrVal::U32(_) => 4,
+            ZephyrVal::I32(_) => 5,
+            ZephyrVal::F32(_) => 6,
+            ZephyrVal::String(_) => 7,
+            ZephyrVal::Bytes(_) => 8,
+            ZephyrVal::Bool(_) => 9,
+            ZephyrVal::Null => 10,
+            ZephyrVal::Array(_) => 11,
+            ZephyrVal::Map(_) => 12,
+        }
+    }
+
+    /// Name of the variant, used in conversion errors.
+    fn variant_name(&self) -> &'static str {
+        match self {
+            ZephyrVal::I128(_) => "I128",
+            ZephyrVal::I64(_) => "I64",
+            ZephyrVal::U64(_) => "U64",
+            ZephyrVal::F64(_) => "F64",
+            ZephyrVal::U32(_) => "U32",
+            ZephyrVal::I32(_) => "I32",
+            ZephyrVal::F32(_) => "F32",
+            ZephyrVal::String(_) => "String",
+            ZephyrVal::Bytes(_) => "Bytes",
+            ZephyrVal::Bool(_) => "Bool",
+            ZephyrVal::Null => "Null",
+            ZephyrVal::Array(_) => "Array",
+            ZephyrVal::Map(_) => "Map",
+        }
+    }
+
+    /// Widens numeric variants to `f64` for cross-type comparisons.
+    fn widened(&self) -> Option<f64> {
+        match self {
+            ZephyrVal::I128(v) => Some(*v as f64),
+            ZephyrVal::I64(v) => Some(*v as f64),
+            ZephyrVal::U64(v) => Some(*v as f64),
+            ZephyrVal::F64(v) => Some(*v),
+            ZephyrVal::U32(v) => Some(*v as f64),
+            ZephyrVal::I32(v) => Some(*v as f64),
+            ZephyrVal::F32(v) => Some(*v as f64),
+            ZephyrVal::String(_)
+            | ZephyrVal::Bytes(_)
+            | ZephyrVal::Bool(_)
+            | ZephyrVal::Null
+            | ZephyrVal::Array(_)
+            | ZephyrVal::Map(_) => None,
+        }
+    }
+}
+
+/// Values of the same variant compare by their inner value (floats use
+/// `total_cmp`). Numeric values of different variants are widened to `f64`
+/// and ties are broken by variant so that only values of the same variant
+/// are ever equal. Any other cross-type comparison orders by variant.
+impl Ord for ZephyrVal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (ZephyrVal::I128(a), ZephyrVal::I128(b)) => a.cmp(b),
+            (ZephyrVal::I64(a), ZephyrVal::I64(b)) => a.cmp(b),
+            (ZephyrVal::U64(a), ZephyrVal::U64(b)) => a.cmp(b),
+            (ZephyrVal::F64(a), ZephyrVal::F64(b)) => a.total_cmp(b),
+            (ZephyrVal::U32(a), ZephyrVal::U32(b)) => a.cmp(b),
+            (ZephyrVal::I32(a), ZephyrVal::I32(b)) => a.cmp(b),
+            (ZephyrVal::F32(a), ZephyrVal::F32(b)) => a.total_cmp(b),
+            (ZephyrVal::String(a), ZephyrVal::String(b)) => a.cmp(b),
+            (ZephyrVal::Bytes(a), ZephyrVal::Bytes(b)) => a.cmp(b),
+            (ZephyrVal::Bool(a), ZephyrVal::Bool(b)) => a.cmp(b),
+            (ZephyrVal::Null, ZephyrVal::Null) => Ordering::Equal,
+            (ZephyrVal::Array(a), ZephyrVal::Array(b)) => a.cmp(b),
+            (ZephyrVal::Map(a), ZephyrVal::Map(b)) => a.cmp(b),
+            _ => match (self.widened(), other.widened()) {
+                (Some(a), Some(b)) => a
+                    .total_cmp(&b)
+                    .then_with(|| self.discriminant().cmp(&other.discriminant())),
+                _ => self.discriminant().cmp(&other.discriminant()),
+            },
+        }
+    }
+}
+
+impl PartialOrd for ZephyrVal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ZephyrVal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ZephyrVal {}
+
+impl Hash for ZephyrVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.discriminant().hash(state);
+
+        match self {
+            ZephyrVal::I128(v) => v.hash(state),
+            ZephyrVal::I64(v) => v.hash(state),
+            ZephyrVal::U64(v) => v.hash(state),
+            ZephyrVal::F64(v) => v.to_bits().hash(state),
+            ZephyrVal::U32(v) => v.hash(state),
+            ZephyrVal::I32(v) => v.hash(state),
+            ZephyrVal::F32(v) => v.to_bits().hash(state),
+            ZephyrVal::String(v) => v.hash(state),
+            ZephyrVal::Bytes(v) => v.hash(state),
+            ZephyrVal::Bool(v) => v.hash(state),
+            ZephyrVal::Null => (),
+            ZephyrVal::Array(v) => v.hash(state),
+            ZephyrVal::Map(v) => v.hash(state),
+        }
+    }
+}
+
+/// Conversions from and to JSON values.
+impl ZephyrVal {
+    /// Converts the value to JSON. Integers that don't fit in JSON numbers
+    /// and bytes (hex-encoded) are converted to strings, non-finite floats
+    /// to `null`.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            ZephyrVal::I128(v) => match i64::try_from(*v) {
+                Ok(v) => Value::from(v),
+                Err(_) => match u64::try_from(*v) {
+                    Ok(v) => Value::from(v),
+                    Err(_) => Value::String(v.to_string()),
+                },
+            },
+            ZephyrVal::I64(v) => Value::from(*v),
+            ZephyrVal::U64(v) => Value::from(*v),
+            ZephyrVal::F64(v) => Value::from(*v),
+            ZephyrVal::U32(v) => Value::from(*v),
+            ZephyrVal::I32(v) => Value::from(*v),
+            ZephyrVal::F32(v) => Value::from(*v),
+            ZephyrVal::String(v) => Value::String(v.clone()),
+            ZephyrVal::Bytes(_) => Value::String(self.to_string()),
+            ZephyrVal::Bool(v) => Value::Bool(*v),
+            ZephyrVal::Null => Value::Null,
+            ZephyrVal::Array(v) => Value::Array(v.iter().map(ZephyrVal::to_json).collect()),
+            ZephyrVal::Map(v) => Value::Object(
+                v.iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Converts a JSON value, objects become maps.
+    pub fn from_json(value: serde_json::Value) -> Self {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => ZephyrVal::Null,
+            Value::Bool(v) => ZephyrVal::Bool(v),
+            Value::Number(v) => {
+                if let Some(v) = v.as_i64() {
+                    ZephyrVal::I64(v)
+                } else if let Some(v) = v.as_u64() {
+                    ZephyrVal::U64(v)
+                } else {
+                    ZephyrVal::F64(v.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::String(v) => ZephyrVal::String(v),
+            Value::Array(v) => ZephyrVal::Array(v.into_iter().map(ZephyrVal::from_json).collect()),
+            Value::Object(v) => ZephyrVal::Map(
+                v.into_iter()
+                    .map(|(key, value)| (key, ZephyrVal::from_json(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Human-readable representation: numbers and booleans as is, strings
+/// quoted, bytes hex-encoded with a `0x` prefix and `null` for `Null`.
+impl fmt::Display for ZephyrVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZephyrVal::I128(v) => write!(f, "{}", v),
+            ZephyrVal::I64(v) => write!(f, "{}", v),
+            ZephyrVal::U64(v) => write!(f, "{}", v),
+            ZephyrVal::F64(v) => write!(f, "{}", v),
+            ZephyrVal::U32(v) => write!(f, "{}", v),
+            ZephyrVal::I32(v) => write!(f, "{}", v),
+            ZephyrVal::F32(v) => write!(f, "{}", v),
+            ZephyrVal::String(v) => write!(f, "{:?}", v),
+            ZephyrVal::Bytes(v) => {
+                write!(f, "0x")?;
+                v.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            }
+            ZephyrVal::Bool(v) => write!(f, "{}", v),
+            ZephyrVal::Null => write!(f, "null"),
+            ZephyrVal::Array(v) => {
+                write!(f, "[")?;
+                for (idx, value) in v.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            ZephyrVal::Map(v) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in v.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
 }
 
-#[derive(Debug)]
+/// Variant name followed by the `Display` value, e.g. `I64(42)`.
+impl fmt::Debug for ZephyrVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZephyrVal::Null => write!(f, "Null"),
+            _ => write!(f, "{}({})", self.variant_name(), self),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum ZephyrValError {
     ConversionError,
+
+    /// The value holds a different variant than the requested one.
+    WrongVariant {
+        expected: &'static str,
+        got: &'static str,
+    },
+}
+
+/// Integers can be read from any integer variant as long as the value
+/// fits in the requested type.
+macro_rules! impl_try_into_integer {
+    ($fn_name:ident, $variant:ident, $inner:ty) => {
+        /// Returns the value converted to the requested integer type. Errors
+        /// with `WrongVariant` if the value isn't an integer, and with
+        /// `ConversionError` if it doesn't fit.
+        pub fn $fn_name(&self) -> Result<$inner, ZephyrValError> {
+            let wide = match self {
+                ZephyrVal::I128(v) => *v,
+                ZephyrVal::I64(v) => *v as i128,
+                ZephyrVal::U64(v) => *v as i128,
+                ZephyrVal::U32(v) => *v as i128,
+                ZephyrVal::I32(v) => *v as i128,
+                _ => return Err(self.wrong_variant(stringify!($variant))),
+            };
+
+            <$inner>::try_from(wide).map_err(|_| ZephyrValError::ConversionError)
+        }
+    };
+}
+
+macro_rules! impl_try_as {
+    ($fn_name:ident, $variant:ident, $inner:ty) => {
+        /// Returns the inner value, or an error if the value holds
+        /// a different variant.
+        pub fn $fn_name(&self) -> Result<$inner, ZephyrValError> {
+            match self {
+                ZephyrVal::$variant(inner_val) => Ok(*inner_val),
+                _ => Err(self.wrong_variant(stringify!($variant))),
+            }
+        }
+    };
+}
+
+impl ZephyrVal {
+    fn wrong_variant(&self, expected: &'static str) -> ZephyrValError {
+        ZephyrValError::WrongVariant {
+            expected,
+            got: self.variant_name(),
+        }
+    }
+
+    impl_try_as!(try_as_i128, I128, i128);
+    impl_try_as!(try_as_i64, I64, i64);
+    impl_try_as!(try_as_u64, U64, u64);
+    impl_try_as!(try_as_f64, F64, f64);
+    impl_try_as!(try_as_u32, U32, u32);
+    impl_try_as!(try_as_i32, I32, i32);
+    impl_try_as!(try_as_f32, F32, f32);
+    impl_try_as!(try_as_bool, Bool, bool);
+
+    impl_try_into_integer!(try_into_i128, I128, i128);
+    impl_try_into_integer!(try_into_i64, I64, i64);
+    impl_try_into_integer!(try_into_u64, U64, u64);
+    impl_try_into_integer!(try_into_u32, U32, u32);
+    impl_try_into_integer!(try_into_i32, I32, i32);
+
+    /// Returns the inner string, or an error if the value holds
+    /// a different variant.
+    pub fn try_as_string(&self) -> Result<&str, ZephyrValError> {
+        match self {
+            ZephyrVal::String(inner_val) => Ok(inner_val),
+            _ => Err(self.wrong_variant("String")),
+        }
+    }
+
+    /// Returns the inner bytes, or an error if the value holds
+    /// a different variant.
+    pub fn try_as_bytes(&self) -> Result<&[u8], ZephyrValError> {
+        match self {
+            ZephyrVal::Bytes(inner_val) => Ok(inner_val),
+            _ => Err(self.wrong_variant("Bytes")),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -103,6 +453,35 @@ pub struct ContractDataEntry {
     pub last_modified: i32,
 }
 
+/// Account entry as returned by the host. Amounts are in stroops.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Account {
+    pub account_id: String,
+    pub native_balance: i64,
+    pub buying_liabilities: i64,
+    pub selling_liabilities: i64,
+    pub seq_num: i64,
+    pub num_subentries: u32,
+    pub num_sponsored: u32,
+    pub num_sponsoring: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TrustlineEntry {
+    pub account_id: AccountId,
+    pub asset: TrustLineAsset,
+    pub entry: LedgerEntry,
+    pub last_modified: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OfferEntry {
+    pub seller_id: AccountId,
+    pub offer_id: i64,
+    pub entry: LedgerEntry,
+    pub last_modified: i32,
+}
+
 macro_rules! impl_inner_from {
     ($variant:ident, $inner:ty) => {
         impl From<$inner> for ZephyrVal {
@@ -111,6 +490,11 @@ macro_rules! impl_inner_from {
             }
         }
 
+        /// Panics if the value holds a different variant.
+        ///
+        /// Deprecated: prefer the non-panicking `ZephyrVal::try_as_*` and
+        /// `ZephyrVal::try_into_*` accessors. Rust rejects `#[deprecated]`
+        /// on trait impls, so the deprecation can only be documented.
         impl From<ZephyrVal> for $inner {
             fn from(value: ZephyrVal) -> Self {
                 match value {
@@ -132,8 +516,240 @@ impl_inner_from!(F32, f32);
 impl_inner_from!(String, String);
 impl_inner_from!(Bytes, Vec<u8>);
 
+impl From<Vec<ZephyrVal>> for ZephyrVal {
+    fn from(value: Vec<ZephyrVal>) -> Self {
+        ZephyrVal::Array(value)
+    }
+}
+
+impl From<Vec<(String, ZephyrVal)>> for ZephyrVal {
+    fn from(value: Vec<(String, ZephyrVal)>) -> Self {
+        ZephyrVal::Map(value)
+    }
+}
+
+impl From<bool> for ZephyrVal {
+    fn from(value: bool) -> Self {
+        ZephyrVal::Bool(value)
+    }
+}
+
+impl TryFrom<ZephyrVal> for bool {
+    type Error = ZephyrValError;
+
+    fn try_from(value: ZephyrVal) -> Result<Self, Self::Error> {
+        match value {
+            ZephyrVal::Bool(inner_val) => Ok(inner_val),
+            other => Err(other.wrong_variant("Bool")),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum RelayedMessageRequest {
     Http(AgnosticRequest),
     Log(ZephyrLog),
 }
+
+#[cfg(test)]
+mod test {
+    use super::{DatabaseError, ZephyrStatus, ZephyrVal, ZephyrValError};
+    use std::collections::HashMap;
+
+    #[test]
+    pub fn database_error_status() {
+        assert!(matches!(
+            ZephyrStatus::from(anyhow::Error::from(DatabaseError::DuplicateKey)),
+            ZephyrStatus::DbWriteError
+        ));
+        assert!(matches!(
+            ZephyrStatus::from(anyhow::Error::from(DatabaseError::NotFound)),
+            ZephyrStatus::NotFound
+        ));
+        assert!(matches!(ZephyrStatus::from(6), ZephyrStatus::NotFound));
+    }
+
+    #[test]
+    pub fn database_error_messages() {
+        assert!(format!("{}", DatabaseError::ZephyrQueryError)
+            .contains("Check that the table and column names exist"));
+        assert!(format!("{}", DatabaseError::DuplicateKey).contains("consider updating it instead"));
+    }
+
+    #[test]
+    pub fn nested_roundtrip() {
+        let value = ZephyrVal::from(vec![
+            (
+                "prices".to_string(),
+                ZephyrVal::from(vec![ZephyrVal::I64(1), ZephyrVal::F64(2.5)]),
+            ),
+            ("asset".to_string(), ZephyrVal::String("XLM".into())),
+            (
+                "extra".to_string(),
+                ZephyrVal::from(vec![("none".to_string(), ZephyrVal::Null)]),
+            ),
+        ]);
+
+        let serialized = bincode::serialize(&value).unwrap();
+        let deserialized: ZephyrVal = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, value);
+
+        let json = serde_json::json!({"asset": "XLM", "prices": [1, 2.5], "extra": {"none": null}});
+        assert_eq!(value.to_json(), json);
+        assert_eq!(ZephyrVal::from_json(json).to_json(), value.to_json());
+    }
+
+    #[test]
+    pub fn display() {
+        assert_eq!(ZephyrVal::I128(-3).to_string(), "-3");
+        assert_eq!(ZephyrVal::I64(42).to_string(), "42");
+        assert_eq!(ZephyrVal::U64(7).to_string(), "7");
+        assert_eq!(ZephyrVal::F64(1.5).to_string(), "1.5");
+        assert_eq!(ZephyrVal::U32(1).to_string(), "1");
+        assert_eq!(ZephyrVal::I32(-1).to_string(), "-1");
+        assert_eq!(ZephyrVal::F32(0.25).to_string(), "0.25");
+        assert_eq!(ZephyrVal::String("hello".into()).to_string(), "\"hello\"");
+        assert_eq!(ZephyrVal::Bytes(vec![1, 2, 255]).to_string(), "0x0102ff");
+        assert_eq!(ZephyrVal::Bool(true).to_string(), "true");
+        assert_eq!(ZephyrVal::Null.to_string(), "null");
+        assert_eq!(
+            ZephyrVal::from(vec![ZephyrVal::I32(1), ZephyrVal::Bool(false)]).to_string(),
+            "[1, false]"
+        );
+        assert_eq!(
+            ZephyrVal::from(vec![("a".to_string(), ZephyrVal::Null)]).to_string(),
+            "{\"a\": null}"
+        );
+
+        assert_eq!(format!("{:?}", ZephyrVal::I64(42)), "I64(42)");
+        assert_eq!(format!("{:?}", ZephyrVal::Null), "Null");
+    }
+
+    #[test]
+    pub fn integer_coercion() {
+        assert_eq!(ZephyrVal::I128(42).try_into_i64(), Ok(42));
+        assert_eq!(
+            ZephyrVal::U64(u64::MAX).try_into_i128(),
+            Ok(u64::MAX as i128)
+        );
+        assert_eq!(ZephyrVal::I32(7).try_into_u32(), Ok(7));
+
+        assert_eq!(
+            ZephyrVal::I128(i128::MAX).try_into_i64(),
+            Err(ZephyrValError::ConversionError)
+        );
+        assert_eq!(
+            ZephyrVal::I64(-1).try_into_u64(),
+            Err(ZephyrValError::ConversionError)
+        );
+        assert_eq!(
+            ZephyrVal::String("1".into()).try_into_i32(),
+            Err(ZephyrValError::WrongVariant {
+                expected: "I32",
+                got: "String"
+            })
+        );
+    }
+
+    #[test]
+    pub fn deprecated_integer_from() {
+        assert_eq!(i64::from(ZephyrVal::I64(-3)), -3);
+        assert_eq!(u32::from(ZephyrVal::U32(3)), 3);
+        assert!(std::panic::catch_unwind(|| i32::from(ZephyrVal::I64(1))).is_err());
+    }
+
+    #[test]
+    pub fn try_as_accessors() {
+        assert_eq!(ZephyrVal::I128(-1).try_as_i128(), Ok(-1));
+        assert_eq!(ZephyrVal::U32(7).try_as_u32(), Ok(7));
+        assert_eq!(ZephyrVal::String("a".into()).try_as_string(), Ok("a"));
+        assert_eq!(ZephyrVal::Bytes(vec![1]).try_as_bytes(), Ok(&[1][..]));
+
+        let wrong = |expected, got| ZephyrValError::WrongVariant { expected, got };
+        let string = ZephyrVal::String("1".into());
+        assert_eq!(string.try_as_i128().unwrap_err(), wrong("I128", "String"));
+        assert_eq!(string.try_as_i64().unwrap_err(), wrong("I64", "String"));
+        assert_eq!(string.try_as_u64().unwrap_err(), wrong("U64", "String"));
+        assert_eq!(string.try_as_f64().unwrap_err(), wrong("F64", "String"));
+        assert_eq!(string.try_as_u32().unwrap_err(), wrong("U32", "String"));
+        assert_eq!(string.try_as_i32().unwrap_err(), wrong("I32", "String"));
+        assert_eq!(string.try_as_f32().unwrap_err(), wrong("F32", "String"));
+        assert_eq!(string.try_as_bool().unwrap_err(), wrong("Bool", "String"));
+        assert_eq!(
+            ZephyrVal::Null.try_as_string(),
+            Err(ZephyrValError::WrongVariant {
+                expected: "String",
+                got: "Null"
+            })
+        );
+        assert_eq!(
+            ZephyrVal::I64(1).try_as_bytes(),
+            Err(ZephyrValError::WrongVariant {
+                expected: "Bytes",
+                got: "I64"
+            })
+        );
+    }
+
+    #[test]
+    pub fn bool_roundtrip() {
+        for value in [true, false] {
+            let serialized = bincode::serialize(&ZephyrVal::from(value)).unwrap();
+            let deserialized: ZephyrVal = bincode::deserialize(&serialized).unwrap();
+
+            assert_eq!(bool::try_from(deserialized).unwrap(), value);
+        }
+
+        assert_eq!(
+            bool::try_from(ZephyrVal::Null),
+            Err(ZephyrValError::WrongVariant {
+                expected: "Bool",
+                got: "Null"
+            })
+        );
+        assert!(bool::try_from(ZephyrVal::I32(1)).is_err());
+    }
+
+    #[test]
+    pub fn numeric_ordering() {
+        assert!(ZephyrVal::I64(-5) < ZephyrVal::I64(3));
+        assert!(ZephyrVal::F64(1.5) < ZephyrVal::F64(2.0));
+        assert!(ZephyrVal::I32(2) < ZephyrVal::F64(2.5));
+        assert!(ZephyrVal::U64(10) > ZephyrVal::I128(-10));
+
+        // Equal widened values of different variants are still distinct.
+        assert!(ZephyrVal::I64(1) != ZephyrVal::F64(1.0));
+        assert!(ZephyrVal::I64(1) == ZephyrVal::I64(1));
+    }
+
+    #[test]
+    pub fn string_and_bytes_ordering() {
+        let mut rows = vec![
+            ZephyrVal::String("charlie".into()),
+            ZephyrVal::String("alpha".into()),
+            ZephyrVal::String("bravo".into()),
+        ];
+        rows.sort();
+
+        assert!(
+            rows == vec![
+                ZephyrVal::String("alpha".into()),
+                ZephyrVal::String("bravo".into()),
+                ZephyrVal::String("charlie".into()),
+            ]
+        );
+        assert!(ZephyrVal::Bytes(vec![0, 255]) < ZephyrVal::Bytes(vec![1]));
+    }
+
+    #[test]
+    pub fn cross_type_ordering() {
+        assert!(ZephyrVal::I64(i64::MAX) < ZephyrVal::String("a".into()));
+        assert!(ZephyrVal::String("zzz".into()) < ZephyrVal::Bytes(vec![]));
+
+        let mut map = HashMap::new();
+        map.insert(ZephyrVal::String("key".into()), 1);
+        map.insert(ZephyrVal::I64(1), 2);
+        assert_eq!(map.get(&ZephyrVal::String("key".into())), Some(&1));
+        assert_eq!(map.get(&ZephyrVal::I64(1)), Some(&2));
+    }
+}
@@ -7,6 +7,7 @@ pub enum LogLevel {
     Error,
     Warning,
     Debug,
+    Info,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -15,3 +16,107 @@ pub struct ZephyrLog {
     pub message: String,
     pub data: Option<Vec<u8>>,
 }
+
+impl ZephyrLog {
+    /// Attaches a key-value pair to the log.
+    ///
+    /// Pairs are stored as a JSON object in `data`, so the log keeps the
+    /// layout the host already decodes. Any `data` that doesn't hold a JSON
+    /// object is replaced.
+    pub fn with_kv(mut self, key: &str, value: impl ToString) -> Self {
+        let mut fields: serde_json::Map<String, serde_json::Value> = self
+            .data
+            .as_deref()
+            .and_then(|data| serde_json::from_slice(data).ok())
+            .unwrap_or_default();
+
+        fields.insert(
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        );
+        self.data = Some(serde_json::to_vec(&fields).unwrap());
+
+        self
+    }
+
+    /// Returns the key-value pairs attached to the log, empty when `data`
+    /// doesn't hold a JSON object.
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let Some(fields) = self.data.as_deref().and_then(|data| {
+            serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(data).ok()
+        }) else {
+            return Vec::new();
+        };
+
+        fields
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(value) => (key, value),
+                other => (key, other.to_string()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LogLevel, ZephyrLog};
+
+    #[test]
+    pub fn kv_roundtrip() {
+        let log = ZephyrLog {
+            level: LogLevel::Info,
+            message: "indexed".into(),
+            data: None,
+        }
+        .with_kv("ledger", 2000)
+        .with_kv(
+            "contract",
+            "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA",
+        );
+
+        let serialized = bincode::serialize(&log).unwrap();
+        let deserialized: ZephyrLog = bincode::deserialize(&serialized).unwrap();
+
+        assert!(matches!(deserialized.level, LogLevel::Info));
+        assert_eq!(deserialized.message, "indexed");
+        assert_eq!(
+            deserialized.fields(),
+            vec![
+                (
+                    "contract".to_string(),
+                    "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA".to_string()
+                ),
+                ("ledger".to_string(), "2000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn wire_layout_unchanged() {
+        #[derive(serde::Serialize)]
+        struct PreviousLog {
+            level: u32,
+            message: String,
+            data: Option<Vec<u8>>,
+        }
+
+        let log = ZephyrLog {
+            level: LogLevel::Debug,
+            message: "indexed".into(),
+            data: None,
+        }
+        .with_kv("ledger", 2000);
+
+        let previous = PreviousLog {
+            level: 2,
+            message: "indexed".into(),
+            data: Some(br#"{"ledger":"2000"}"#.to_vec()),
+        };
+
+        assert_eq!(
+            bincode::serialize(&log).unwrap(),
+            bincode::serialize(&previous).unwrap()
+        );
+    }
+}
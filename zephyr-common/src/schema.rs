@@ -0,0 +1,148 @@
+//! DDL of the tables Zephyr programs read and write.
+//!
+//! Shared by the `DatabaseDerive` macro and the Mercury CLI so that both
+//! generate the same statements.
+
+use serde::{Deserialize, Serialize};
+
+/// Type of every table column. The SDK writes each value either as a
+/// bincode-serialized `ZephyrVal` or as raw XDR bytes.
+pub const COLUMN_TYPE: &str = "BYTEA";
+
+/// Column of a table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnSchema<'a> {
+    pub name: &'a str,
+    pub primary_key: bool,
+    pub indexed: bool,
+}
+
+/// Table as declared in the `[[tables]]` of a `zephyr.toml`.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// Column of a [`Table`].
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    pub col_type: String,
+
+    /// Whether the column is the table's primary key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary: Option<bool>,
+
+    /// Whether the column is indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<bool>,
+}
+
+/// Generates the `CREATE TABLE IF NOT EXISTS` statement of a table followed
+/// by a `CREATE INDEX IF NOT EXISTS` statement for each indexed column, one
+/// statement per line.
+///
+/// A single primary key column is declared inline, several ones form a
+/// composite `PRIMARY KEY (...)` constraint.
+pub fn table_ddl<'a>(table: &str, columns: impl IntoIterator<Item = ColumnSchema<'a>>) -> String {
+    let columns: Vec<ColumnSchema> = columns.into_iter().collect();
+    let primary_keys: Vec<&str> = columns
+        .iter()
+        .filter(|column| column.primary_key)
+        .map(|column| column.name)
+        .collect();
+
+    let mut definitions = Vec::new();
+    let mut indexes = Vec::new();
+
+    for column in &columns {
+        let primary_key = if primary_keys.len() == 1 && column.primary_key {
+            " PRIMARY KEY"
+        } else {
+            ""
+        };
+        definitions.push(format!("{} {}{}", column.name, COLUMN_TYPE, primary_key));
+
+        if column.indexed {
+            indexes.push(format!(
+                "CREATE INDEX IF NOT EXISTS {table}_{column}_idx ON {table} ({column});",
+                table = table,
+                column = column.name
+            ));
+        }
+    }
+
+    if primary_keys.len() > 1 {
+        definitions.push(format!("PRIMARY KEY ({})", primary_keys.join(", ")));
+    }
+
+    let mut ddl = format!(
+        "CREATE TABLE IF NOT EXISTS {} ({});",
+        table,
+        definitions.join(", ")
+    );
+    for index in indexes {
+        ddl.push('\n');
+        ddl.push_str(&index);
+    }
+
+    ddl
+}
+
+#[cfg(test)]
+mod test {
+    use super::{table_ddl, ColumnSchema};
+
+    #[test]
+    pub fn ddl_generation() {
+        let columns = [
+            ColumnSchema {
+                name: "hash",
+                primary_key: true,
+                indexed: false,
+            },
+            ColumnSchema {
+                name: "ledger",
+                primary_key: false,
+                indexed: true,
+            },
+            ColumnSchema {
+                name: "amount",
+                primary_key: false,
+                indexed: false,
+            },
+        ];
+
+        assert_eq!(
+            table_ddl("transfers", columns),
+            "CREATE TABLE IF NOT EXISTS transfers (hash BYTEA PRIMARY KEY, ledger BYTEA, amount BYTEA);\n\
+             CREATE INDEX IF NOT EXISTS transfers_ledger_idx ON transfers (ledger);"
+        );
+        assert_eq!(
+            table_ddl("empty", []),
+            "CREATE TABLE IF NOT EXISTS empty ();"
+        );
+    }
+
+    #[test]
+    pub fn composite_primary_key() {
+        let column = |name, primary_key| ColumnSchema {
+            name,
+            primary_key,
+            indexed: false,
+        };
+
+        assert_eq!(
+            table_ddl(
+                "balances",
+                [
+                    column("account", true),
+                    column("asset", true),
+                    column("balance", false)
+                ]
+            ),
+            "CREATE TABLE IF NOT EXISTS balances (account BYTEA, asset BYTEA, balance BYTEA, PRIMARY KEY (account, asset));"
+        );
+    }
+}
@@ -1,6 +1,12 @@
 #![allow(missing_docs)]
 
-use charming_fork_zephyr::Chart;
+use charming_fork_zephyr::{
+    component::{Axis, Title as ChartTitle},
+    datatype::{CompositeValue, DataPointItem},
+    series::{Pie, Scatter},
+    Chart,
+};
+use core::fmt::Display;
 use serde::Serialize;
 pub use table::Table;
 
@@ -19,6 +25,8 @@ pub enum ChartType {
     Chart,
     #[serde(rename = "table")]
     Table,
+    #[serde(rename = "metric")]
+    Metric,
 }
 
 #[derive(Serialize)]
@@ -28,6 +36,27 @@ pub enum ChartTypeWrapped {
 
     #[serde(rename = "inner")]
     Table(Table),
+
+    #[serde(rename = "inner")]
+    Metric(MetricCard),
+}
+
+/// A single KPI value, e.g. the total TVL.
+#[derive(Serialize, Default)]
+pub struct MetricCard {
+    title: String,
+    value: String,
+    subtitle: Option<String>,
+}
+
+impl MetricCard {
+    pub fn new(title: impl ToString, value: impl Display, subtitle: Option<&str>) -> Self {
+        Self {
+            title: title.to_string(),
+            value: value.to_string(),
+            subtitle: subtitle.map(|subtitle| subtitle.to_string()),
+        }
+    }
 }
 
 #[derive(Serialize, Default)]
@@ -65,6 +94,105 @@ impl Dashboard {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Adds a pie chart with a `(name, value)` slice for each element of `data`.
+    pub fn add_pie_chart(self, title: impl ToString, data: Vec<(&str, f64)>) -> Self {
+        let data: Vec<DataPointItem> = data
+            .into_iter()
+            .map(|(name, value)| DataPointItem::new(chart_value(value)).name(name))
+            .collect();
+        let chart = Chart::new()
+            .title(ChartTitle::new().text(title.to_string()))
+            .series(Pie::new().data(data));
+
+        self.entry(DashboardEntry::new().title(title).chart(chart))
+    }
+
+    /// Adds a scatter plot of `(x, y)` points.
+    pub fn add_scatter_chart(
+        self,
+        title: impl ToString,
+        x_label: impl ToString,
+        y_label: impl ToString,
+        points: Vec<(f64, f64)>,
+    ) -> Self {
+        let data: Vec<Vec<CompositeValue>> = points
+            .into_iter()
+            .map(|(x, y)| vec![chart_value(x), chart_value(y)])
+            .collect();
+        let chart = Chart::new()
+            .title(ChartTitle::new().text(title.to_string()))
+            .x_axis(Axis::new().name(x_label.to_string()))
+            .y_axis(Axis::new().name(y_label.to_string()))
+            .series(Scatter::new().data(data));
+
+        self.entry(DashboardEntry::new().title(title).chart(chart))
+    }
+
+    /// Adds a table with the provided columns and rows showing `limit`
+    /// rows per page, 10 when `None`.
+    pub fn add_table(
+        self,
+        title: impl ToString,
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+        limit: Option<i32>,
+    ) -> Self {
+        let mut table = rows
+            .into_iter()
+            .fold(Table::new().columns(columns), Table::row);
+        if let Some(limit) = limit {
+            table = table.paginate(limit);
+        }
+
+        self.entry(DashboardEntry::new().title(title).table(table))
+    }
+
+    /// Adds a table of the rows whose cell at `filter`'s column contains its
+    /// text, sorted by `sort`'s column (ascending when `true`) and reduced to
+    /// `page`'s zero-based page of the given number of rows. See
+    /// [`Table::filter`], [`Table::sort`] and [`Table::page`].
+    pub fn add_table_view(
+        self,
+        title: impl ToString,
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+        filter: Option<(usize, &str)>,
+        sort: Option<(usize, bool)>,
+        page: Option<(usize, usize)>,
+    ) -> Self {
+        let mut table = rows
+            .into_iter()
+            .fold(Table::new().columns(columns), Table::row);
+        if let Some((column_index, contains)) = filter {
+            table = table.filter(column_index, contains);
+        }
+        if let Some((column_index, ascending)) = sort {
+            table = table.sort(column_index, ascending);
+        }
+        if let Some((page, per_page)) = page {
+            table = table.page(page, per_page);
+        }
+
+        self.entry(DashboardEntry::new().title(title).table(table))
+    }
+
+    /// Adds a card showing a single value.
+    pub fn add_metric_card(
+        self,
+        title: impl ToString,
+        value: impl Display,
+        subtitle: Option<&str>,
+    ) -> Self {
+        let card = MetricCard::new(title.to_string(), value, subtitle);
+        self.entry(DashboardEntry::new().title(title).metric(card))
+    }
+}
+
+/// Numeric chart values of charming are integers, so values are passed as
+/// strings to keep their decimals, ECharts reads numeric strings as numbers.
+fn chart_value(value: f64) -> CompositeValue {
+    CompositeValue::String(value.to_string())
 }
 
 impl DashboardEntry {
@@ -100,4 +228,147 @@ impl DashboardEntry {
 
         self
     }
+
+    pub fn metric(mut self, card: MetricCard) -> Self {
+        self.chart_type = Some(ChartType::Metric);
+        self.inner = Some(ChartTypeWrapped::Metric(card));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::Dashboard;
+
+    #[test]
+    pub fn pie_chart_serialization() {
+        let dashboard =
+            Dashboard::new().add_pie_chart("Shares", vec![("XLM", 60.5), ("USDC", 39.5)]);
+
+        assert_eq!(
+            serde_json::to_value(&dashboard).unwrap()["data"],
+            json!([{
+                "type": "chart",
+                "title": { "text": "Shares" },
+                "height": "300px",
+                "width": "1000px",
+                "inner": {
+                    "title": [{ "text": "Shares" }],
+                    "series": [{
+                        "type": "pie",
+                        "data": [
+                            { "value": "60.5", "name": "XLM" },
+                            { "value": "39.5", "name": "USDC" }
+                        ]
+                    }]
+                }
+            }])
+        );
+    }
+
+    #[test]
+    pub fn scatter_chart_serialization() {
+        let dashboard = Dashboard::new().add_scatter_chart(
+            "Volume",
+            "price",
+            "volume",
+            vec![(1.5, 2.0), (0.25, 10.0)],
+        );
+
+        assert_eq!(
+            serde_json::to_value(&dashboard).unwrap()["data"],
+            json!([{
+                "type": "chart",
+                "title": { "text": "Volume" },
+                "height": "300px",
+                "width": "1000px",
+                "inner": {
+                    "title": [{ "text": "Volume" }],
+                    "xAxis": [{ "name": "price" }],
+                    "yAxis": [{ "name": "volume" }],
+                    "series": [{
+                        "type": "scatter",
+                        "data": [["1.5", "2"], ["0.25", "10"]]
+                    }]
+                }
+            }])
+        );
+    }
+
+    #[test]
+    pub fn metric_card_serialization() {
+        let dashboard = Dashboard::new()
+            .add_metric_card("TVL", 1200, Some("USD"))
+            .add_metric_card("Users", 42, None);
+
+        assert_eq!(
+            serde_json::to_value(&dashboard).unwrap()["data"],
+            json!([
+                {
+                    "type": "metric",
+                    "title": { "text": "TVL" },
+                    "height": "300px",
+                    "width": "1000px",
+                    "inner": { "title": "TVL", "value": "1200", "subtitle": "USD" }
+                },
+                {
+                    "type": "metric",
+                    "title": { "text": "Users" },
+                    "height": "300px",
+                    "width": "1000px",
+                    "inner": { "title": "Users", "value": "42", "subtitle": null }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    pub fn table_serialization() {
+        let dashboard = Dashboard::new().add_table(
+            "Balances",
+            vec!["account".into(), "balance".into()],
+            vec![vec!["GA".into(), "10".into()]],
+            Some(25),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&dashboard).unwrap()["data"],
+            json!([{
+                "type": "table",
+                "title": { "text": "Balances" },
+                "height": "300px",
+                "width": "1000px",
+                "inner": {
+                    "columns": ["account", "balance"],
+                    "data": [["GA", "10"]],
+                    "style": { "table": { "font-size": "12px" } },
+                    "pagination": { "summary": true, "limit": 25 }
+                }
+            }])
+        );
+    }
+
+    #[test]
+    pub fn table_view() {
+        let rows = ["bob", "alice", "carol", "albert"]
+            .into_iter()
+            .map(|name| vec![name.to_string()])
+            .collect();
+        let dashboard = Dashboard::new().add_table_view(
+            "Accounts",
+            vec!["name".into()],
+            rows,
+            Some((0, "l")),
+            Some((0, true)),
+            Some((0, 2)),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&dashboard).unwrap()["data"][0]["inner"]["data"],
+            json!([["albert"], ["alice"]])
+        );
+    }
 }
@@ -44,4 +44,129 @@ impl Table {
         self.data.push(row);
         self
     }
+
+    /// Sets the number of rows shown per page.
+    pub fn paginate(mut self, limit: i32) -> Self {
+        self.pagination.limit = limit;
+        self
+    }
+
+    /// Sets whether the pagination summary is shown.
+    pub fn show_summary(mut self, summary: bool) -> Self {
+        self.pagination.summary = summary;
+        self
+    }
+
+    /// Shows all rows in a single page.
+    pub fn no_pagination(mut self) -> Self {
+        self.pagination.limit = i32::MAX;
+        self
+    }
+
+    /// Sorts the rows lexicographically by the cell at `column_index`.
+    pub fn sort(mut self, column_index: usize, ascending: bool) -> Self {
+        self.data.sort_by(|a, b| {
+            let ordering = a.get(column_index).cmp(&b.get(column_index));
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        self
+    }
+
+    /// Keeps only the rows whose cell at `column_index` contains `contains`.
+    pub fn filter(mut self, column_index: usize, contains: &str) -> Self {
+        self.data.retain(|row| {
+            row.get(column_index)
+                .is_some_and(|cell| cell.contains(contains))
+        });
+        self
+    }
+
+    /// Keeps only the rows of the zero-based `page` of `per_page` rows.
+    ///
+    /// Unlike [`Table::paginate`], which sets how the rows are paginated
+    /// in the dashboard, this drops the rows outside of the page.
+    pub fn page(mut self, page: usize, per_page: usize) -> Self {
+        let start = page.saturating_mul(per_page).min(self.data.len());
+        let end = start.saturating_add(per_page).min(self.data.len());
+        self.data = self.data.drain(start..end).collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::Table;
+
+    #[test]
+    pub fn pagination_serialization() {
+        assert_eq!(
+            serde_json::to_value(Table::new().paginate(25).show_summary(false)).unwrap(),
+            json!({
+                "columns": [],
+                "data": [],
+                "style": { "table": { "font-size": "12px" } },
+                "pagination": { "summary": false, "limit": 25 }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(Table::new().no_pagination()).unwrap()["pagination"],
+            json!({ "summary": true, "limit": i32::MAX })
+        );
+    }
+
+    fn names(names: &[&str]) -> Table {
+        names.iter().fold(Table::new(), |table, name| {
+            table.row(vec![name.to_string()])
+        })
+    }
+
+    fn cells(table: &Table) -> Vec<&str> {
+        table.data.iter().map(|row| row[0].as_str()).collect()
+    }
+
+    #[test]
+    pub fn sort_order() {
+        let table = names(&["bob", "alice", "Carol", "10", "9"]);
+        assert_eq!(
+            cells(&table.sort(0, true)),
+            vec!["10", "9", "Carol", "alice", "bob"]
+        );
+
+        let table = names(&["bob", "alice", "carol"]);
+        assert_eq!(cells(&table.sort(0, false)), vec!["carol", "bob", "alice"]);
+
+        // Rows missing the column sort first.
+        let table = names(&["bob"]).row(vec![]).sort(0, true);
+        assert!(table.data[0].is_empty());
+    }
+
+    #[test]
+    pub fn filtering() {
+        let table = names(&["alice", "bob", "albert"]).row(vec![]);
+        assert_eq!(cells(&table.filter(0, "al")), vec!["alice", "albert"]);
+
+        let table = names(&["alice", "bob"]);
+        assert!(table.filter(0, "z").data.is_empty());
+
+        let table = names(&["alice", "bob"]);
+        assert!(table.filter(1, "").data.is_empty());
+    }
+
+    #[test]
+    pub fn page_boundaries() {
+        let rows = ["a", "b", "c", "d", "e"];
+
+        assert_eq!(cells(&names(&rows).page(0, 2)), vec!["a", "b"]);
+        assert_eq!(cells(&names(&rows).page(2, 2)), vec!["e"]);
+        assert!(names(&rows).page(3, 2).data.is_empty());
+        assert!(names(&rows).page(usize::MAX, 2).data.is_empty());
+        assert!(names(&rows).page(0, 0).data.is_empty());
+        assert!(names(&["a", "b"]).page(1, 2).data.is_empty());
+    }
 }
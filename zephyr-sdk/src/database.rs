@@ -1,11 +1,14 @@
 use crate::{
     env::EnvClient,
-    external::{env_push_stack, read_as_id, read_raw, update_raw, write_raw},
+    external::{
+        count_raw, delete_raw, env_push_stack, read_as_id, read_as_id_paginated, read_raw,
+        read_raw_paginated, update_raw, upsert_raw, write_raw,
+    },
     symbol, to_fixed, SdkError,
 };
 use rs_zephyr_common::ZephyrVal;
 use serde::{Deserialize, Serialize};
-use soroban_sdk::xdr::{Limits, WriteXdr};
+use soroban_sdk::xdr::{Limits, ReadXdr, WriteXdr};
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct TypeWrap(pub Vec<u8>);
@@ -20,6 +23,49 @@ impl TypeWrap {
         let bytes = to_fixed::<u8, 8>(self.0.clone());
         u64::from_be_bytes(bytes)
     }
+
+    /// Decodes the column as a bincode-serialized [`ZephyrVal`].
+    fn to_zephyr_val(&self) -> ZephyrVal {
+        bincode::deserialize(&self.0)
+            .unwrap_or_else(|_| panic!("Column is not a serialized ZephyrVal"))
+    }
+
+    /// Reads a `ZephyrVal::F64` column.
+    pub fn to_f64(&self) -> f64 {
+        self.to_zephyr_val()
+            .try_as_f64()
+            .unwrap_or_else(|e| panic!("Cannot read column as f64: {:?}", e))
+    }
+
+    /// Reads a `ZephyrVal::I32` column.
+    pub fn to_i32(&self) -> i32 {
+        self.to_zephyr_val()
+            .try_as_i32()
+            .unwrap_or_else(|e| panic!("Cannot read column as i32: {:?}", e))
+    }
+
+    /// Reads a `ZephyrVal::U32` column.
+    pub fn to_u32(&self) -> u32 {
+        self.to_zephyr_val()
+            .try_as_u32()
+            .unwrap_or_else(|e| panic!("Cannot read column as u32: {:?}", e))
+    }
+
+    /// Reads a `ZephyrVal::Bool` column.
+    pub fn to_bool(&self) -> bool {
+        self.to_zephyr_val()
+            .try_as_bool()
+            .unwrap_or_else(|e| panic!("Cannot read column as bool: {:?}", e))
+    }
+
+    /// Reads a `ZephyrVal::String` column.
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.to_zephyr_val()
+            .try_as_string()
+            .map(str::to_string)
+            .unwrap_or_else(|e| panic!("Cannot read column as String: {:?}", e))
+    }
 }
 
 /// Object returned by database reads.
@@ -30,11 +76,121 @@ pub struct TableRows {
     pub rows: Vec<TableRow>,
 }
 
+impl TableRows {
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Deserializes the rows into `T`, whose columns must match the
+    /// columns the rows were read with.
+    pub fn into_typed<T: DatabaseInteract>(self, env: &EnvClient) -> Vec<T> {
+        T::from_rows(env, self)
+    }
+}
+
+impl IntoIterator for TableRows {
+    type Item = TableRow;
+    type IntoIter = std::vec::IntoIter<TableRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TableRows {
+    type Item = &'a TableRow;
+    type IntoIter = core::slice::Iter<'a, TableRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
 /// Condition clauses that can be applied when reading the
 /// database.
+///
+/// Conditions are serializable so that they can be built outside of the
+/// program, e.g. sent as part of a serverless function's arguments.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum Condition {
     /// A given column is equal to a certain object.
     ColumnEqualTo(String, Vec<u8>),
+
+    /// A given column is equal to any of the provided objects.
+    ColumnIn(String, Vec<Vec<u8>>),
+
+    /// A given column is greater than a certain object.
+    ColumnGreaterThan(String, Vec<u8>),
+
+    /// A given column is less than a certain object.
+    ColumnLessThan(String, Vec<u8>),
+
+    /// A given column is not equal to a certain object.
+    ColumnNotEqualTo(String, Vec<u8>),
+}
+
+impl Condition {
+    /// Parses a JSON condition, e.g. `{"ColumnEqualTo": ["hash", [1, 2]]}`.
+    pub fn from_json(json: &str) -> Result<Self, SdkError> {
+        serde_json::from_str(json).map_err(|_| SdkError::Conversion)
+    }
+
+    /// Returns the column name, the host operator code and the
+    /// serialized arguments of the condition.
+    ///
+    /// The codes match the host's condition operators:
+    ///
+    /// | Condition           | Code | SQL       |
+    /// |---------------------|------|-----------|
+    /// | `ColumnEqualTo`     | 0    | `=`       |
+    /// | `ColumnGreaterThan` | 1    | `>`       |
+    /// | `ColumnLessThan`    | 2    | `<`       |
+    /// | `ColumnNotEqualTo`  | 3    | `<>`      |
+    /// | `ColumnIn`          | 4    | `IN (..)` |
+    fn to_parts(&self) -> (&String, i64, Vec<&[u8]>) {
+        match self {
+            Condition::ColumnEqualTo(colname, value) => (colname, 0, vec![value.as_slice()]),
+            Condition::ColumnGreaterThan(colname, value) => (colname, 1, vec![value.as_slice()]),
+            Condition::ColumnLessThan(colname, value) => (colname, 2, vec![value.as_slice()]),
+            Condition::ColumnNotEqualTo(colname, value) => (colname, 3, vec![value.as_slice()]),
+            Condition::ColumnIn(colname, values) => {
+                (colname, 4, values.iter().map(Vec::as_slice).collect())
+            }
+        }
+    }
+}
+
+/// Limit, offset and ordering applied when reading the database.
+#[derive(Clone, Default)]
+pub struct ReadOptions {
+    /// Maximum number of rows returned.
+    pub limit: Option<u64>,
+
+    /// Number of rows skipped.
+    pub offset: Option<u64>,
+
+    /// Column to order by and whether the order is ascending.
+    pub order_by: Option<(String, bool)>,
+}
+
+impl ReadOptions {
+    /// Options reading only the first row.
+    pub(crate) fn first_row() -> Self {
+        Self {
+            limit: Some(1),
+            ..Default::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.limit.is_none() && self.offset.is_none() && self.order_by.is_none()
+    }
 }
 
 /// Wraps a single row.
@@ -44,8 +200,28 @@ pub struct TableRow {
     pub row: Vec<TypeWrap>,
 }
 
+impl TableRow {
+    fn column_bytes(&self, idx: usize) -> Result<&[u8], SdkError> {
+        self.row
+            .get(idx)
+            .map(|column| column.0.as_slice())
+            .ok_or(SdkError::Conversion)
+    }
+
+    /// Deserializes the bincode-encoded column at `idx`, e.g. a [`ZephyrVal`].
+    pub fn get_column<T: for<'de> Deserialize<'de>>(&self, idx: usize) -> Result<T, SdkError> {
+        bincode::deserialize(self.column_bytes(idx)?).map_err(|_| SdkError::Conversion)
+    }
+
+    /// Decodes the XDR-encoded column at `idx`, e.g. an `ScVal`.
+    pub fn get_column_xdr<T: ReadXdr>(&self, idx: usize) -> Result<T, SdkError> {
+        T::from_xdr(self.column_bytes(idx)?, Limits::none()).map_err(|_| SdkError::Conversion)
+    }
+}
+
 mod unsafe_helpers {
-    use crate::external::env_push_stack;
+    use super::{Condition, ReadOptions};
+    use crate::{external::env_push_stack, symbol};
 
     pub(crate) unsafe fn push_head(table_name: i64, columns: Vec<i64>) {
         env_push_stack(table_name as i64);
@@ -64,6 +240,60 @@ mod unsafe_helpers {
             env_push_stack(segment.1);
         }
     }
+
+    /// Pushes the limit (`-1` when unset), the offset, the order by column
+    /// (`0` when unset) and whether the order is ascending to the stack.
+    pub(crate) unsafe fn push_read_options(options: &ReadOptions) {
+        env_push_stack(options.limit.map(|limit| limit as i64).unwrap_or(-1));
+        env_push_stack(options.offset.unwrap_or(0) as i64);
+
+        if let Some((column, ascending)) = &options.order_by {
+            env_push_stack(symbol::Symbol::try_from_bytes(column.as_bytes()).unwrap().0 as i64);
+            env_push_stack(*ascending as i64);
+        } else {
+            env_push_stack(0);
+            env_push_stack(1);
+        }
+    }
+
+    /// Pushes the conditions to the stack, followed by their arguments.
+    ///
+    /// Every condition pushes its column and operator code. Then, for each
+    /// condition in the same order, `ColumnIn` pushes the number of values
+    /// followed by a pointer and length per value, while the other
+    /// conditions push the pointer and length of their single argument.
+    /// The arguments are borrowed from `conditions`, which must be kept
+    /// alive until the host function is called.
+    pub(crate) unsafe fn push_conditions(conditions: &[Condition]) {
+        env_push_stack(conditions.len() as i64);
+
+        let mut args = Vec::new();
+        for cond in conditions {
+            let (colname, operator, values) = cond.to_parts();
+
+            env_push_stack(
+                symbol::Symbol::try_from_bytes(colname.as_bytes())
+                    .unwrap()
+                    .0 as i64,
+            );
+            env_push_stack(operator);
+
+            args.push((matches!(cond, Condition::ColumnIn(..)), values))
+        }
+
+        env_push_stack(args.len() as i64);
+
+        for (is_list, values) in args {
+            if is_list {
+                env_push_stack(values.len() as i64);
+            }
+
+            for value in values {
+                env_push_stack(value.as_ptr() as i64);
+                env_push_stack(value.len() as i64);
+            }
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -75,6 +305,7 @@ impl Database {
         columns: &[&str],
         external_id: Option<i64>,
         conditions: Option<&[Condition]>,
+        options: Option<&ReadOptions>,
     ) -> Result<TableRows, SdkError> {
         let table_name = symbol::Symbol::try_from_bytes(table_name.as_bytes()).unwrap();
         let cols = columns
@@ -84,39 +315,30 @@ impl Database {
 
         unsafe { unsafe_helpers::push_head(table_name.0 as i64, cols) }
 
-        if let Some(conditions) = conditions {
+        let options = options.filter(|options| !options.is_empty());
+
+        let (status, offset, size) = if let Some(options) = options {
+            // The paginated reads always expect the conditions on the stack.
             unsafe {
-                env_push_stack(conditions.len() as i64);
-
-                let mut args = Vec::new();
-                for cond in conditions {
-                    let (colname, operator, value) = match cond {
-                        Condition::ColumnEqualTo(colname, value) => (colname, 0, value),
-                    };
-
-                    env_push_stack(
-                        symbol::Symbol::try_from_bytes(colname.as_bytes())
-                            .unwrap()
-                            .0 as i64,
-                    );
-                    env_push_stack(operator as i64);
-
-                    args.push((value.as_ptr() as i64, value.len() as i64))
-                }
-
-                env_push_stack(args.len() as i64);
-
-                for segment in args {
-                    env_push_stack(segment.0);
-                    env_push_stack(segment.1);
-                }
+                unsafe_helpers::push_conditions(conditions.unwrap_or(&[]));
+                unsafe_helpers::push_read_options(options);
             }
-        };
 
-        let (status, offset, size) = if let Some(external) = external_id {
-            unsafe { read_as_id(external) }
+            if let Some(external) = external_id {
+                unsafe { read_as_id_paginated(external) }
+            } else {
+                unsafe { read_raw_paginated() }
+            }
         } else {
-            unsafe { read_raw() }
+            if let Some(conditions) = conditions {
+                unsafe { unsafe_helpers::push_conditions(conditions) }
+            }
+
+            if let Some(external) = external_id {
+                unsafe { read_as_id(external) }
+            } else {
+                unsafe { read_raw() }
+            }
         };
         SdkError::express_from_status(status)?;
 
@@ -135,6 +357,29 @@ impl Database {
         Ok(table)
     }
 
+    pub fn count_table(
+        table_name: &str,
+        columns: &[&str],
+        conditions: Option<&[Condition]>,
+    ) -> Result<u64, SdkError> {
+        let table_name = symbol::Symbol::try_from_bytes(table_name.as_bytes()).unwrap();
+        let cols = columns
+            .iter()
+            .map(|col| symbol::Symbol::try_from_bytes(col.as_bytes()).unwrap().0 as i64)
+            .collect::<Vec<i64>>();
+
+        unsafe { unsafe_helpers::push_head(table_name.0 as i64, cols) }
+
+        if let Some(conditions) = conditions {
+            unsafe { unsafe_helpers::push_conditions(conditions) }
+        }
+
+        let (status, count) = unsafe { count_raw() };
+        SdkError::express_from_status(status)?;
+
+        Ok(count as u64)
+    }
+
     pub fn write_table(
         table_name: &str,
         columns: &[&str],
@@ -160,6 +405,41 @@ impl Database {
         SdkError::express_from_status(status)
     }
 
+    pub fn upsert_table(
+        table_name: &str,
+        columns: &[&str],
+        segments: &[&[u8]],
+        conflict_columns: &[&str],
+    ) -> Result<(), SdkError> {
+        let table_name = symbol::Symbol::try_from_bytes(table_name.as_bytes()).unwrap();
+        let cols = columns
+            .iter()
+            .map(|col| symbol::Symbol::try_from_bytes(col.as_bytes()).unwrap().0 as i64)
+            .collect::<Vec<i64>>();
+        let conflict_cols = conflict_columns
+            .iter()
+            .map(|col| symbol::Symbol::try_from_bytes(col.as_bytes()).unwrap().0 as i64)
+            .collect::<Vec<i64>>();
+
+        let segments = segments
+            .iter()
+            .map(|segment| (segment.as_ptr() as i64, segment.len() as i64))
+            .collect::<Vec<(i64, i64)>>();
+
+        unsafe {
+            unsafe_helpers::push_head(table_name.0 as i64, cols);
+            unsafe_helpers::push_data_segments(segments);
+
+            env_push_stack(conflict_cols.len() as i64);
+            for col in conflict_cols {
+                env_push_stack(col)
+            }
+        }
+
+        let status = unsafe { upsert_raw() };
+        SdkError::express_from_status(status)
+    }
+
     pub fn update_table(
         table_name: &str,
         columns: &[&str],
@@ -180,34 +460,22 @@ impl Database {
         unsafe {
             unsafe_helpers::push_head(table_name.0 as i64, cols);
             unsafe_helpers::push_data_segments(segments);
+            unsafe_helpers::push_conditions(conditions)
+        }
 
-            env_push_stack(conditions.len() as i64);
-
-            let mut args = Vec::new();
-            for cond in conditions {
-                let (colname, operator, value) = match cond {
-                    Condition::ColumnEqualTo(colname, value) => (colname, 0, value),
-                };
-
-                env_push_stack(
-                    symbol::Symbol::try_from_bytes(colname.as_bytes())
-                        .unwrap()
-                        .0 as i64,
-                );
-                env_push_stack(operator as i64);
-
-                args.push((value.as_ptr() as i64, value.len() as i64))
-            }
+        let status = unsafe { update_raw() };
+        SdkError::express_from_status(status)
+    }
 
-            env_push_stack(args.len() as i64);
+    pub fn delete_table(table_name: &str, conditions: &[Condition]) -> Result<(), SdkError> {
+        let table_name = symbol::Symbol::try_from_bytes(table_name.as_bytes()).unwrap();
 
-            for segment in args {
-                env_push_stack(segment.0);
-                env_push_stack(segment.1);
-            }
+        unsafe {
+            unsafe_helpers::push_head(table_name.0 as i64, vec![]);
+            unsafe_helpers::push_conditions(conditions)
         }
 
-        let status = unsafe { update_raw() };
+        let status = unsafe { delete_raw() };
         SdkError::express_from_status(status)
     }
 }
@@ -216,11 +484,23 @@ impl Database {
 pub(crate) enum Action {
     Read,
     Update,
+    Delete,
+}
+
+/// Converts the argument to a ZephyrVal and serializes it.
+fn serialize_as_val<T: TryInto<ZephyrVal>>(argument: T) -> Vec<u8> {
+    bincode::serialize(
+        &TryInto::<ZephyrVal>::try_into(argument)
+            .map_err(|_| ())
+            .unwrap(),
+    )
+    .unwrap()
 }
 
 /// Simple wrapper for building conditions.
 pub struct TableQueryWrapper {
     conditions: Vec<Condition>,
+    options: ReadOptions,
     action: Action,
 }
 
@@ -229,10 +509,29 @@ impl TableQueryWrapper {
     pub(crate) fn new(action: Action) -> Self {
         Self {
             conditions: vec![],
+            options: ReadOptions::default(),
             action,
         }
     }
 
+    /// Limits the number of rows returned by the read.
+    pub fn limit(&mut self, n: u64) -> &mut Self {
+        self.options.limit = Some(n);
+        self
+    }
+
+    /// Skips the first `n` rows of the read.
+    pub fn offset(&mut self, n: u64) -> &mut Self {
+        self.options.offset = Some(n);
+        self
+    }
+
+    /// Orders the rows of the read by the given column.
+    pub fn order_by(&mut self, column: impl ToString, ascending: bool) -> &mut Self {
+        self.options.order_by = Some((column.to_string(), ascending));
+        self
+    }
+
     /// Adds a new condition in the update according to which a given column
     /// should be equal to an XDR object.
     pub fn column_equal_to_xdr(&mut self, column: impl ToString, xdr: &impl WriteXdr) -> &mut Self {
@@ -265,13 +564,73 @@ impl TableQueryWrapper {
         column: impl ToString,
         argument: T,
     ) -> &mut Self {
-        let argument = bincode::serialize(
-            &TryInto::<ZephyrVal>::try_into(argument)
-                .map_err(|_| ())
-                .unwrap(),
-        )
-        .unwrap();
-        let condition = Condition::ColumnEqualTo(column.to_string(), argument);
+        let condition = Condition::ColumnEqualTo(column.to_string(), serialize_as_val(argument));
+        self.conditions.push(condition);
+
+        self
+    }
+
+    /// Adds a new condition according to which a given column should not
+    /// be equal to the matching object.
+    ///
+    /// Under the hood, the object is converted to a ZephyrVal and is later
+    /// serialized.
+    pub fn column_not_equal_to<T: Serialize + TryInto<ZephyrVal>>(
+        &mut self,
+        column: impl ToString,
+        argument: T,
+    ) -> &mut Self {
+        let condition = Condition::ColumnNotEqualTo(column.to_string(), serialize_as_val(argument));
+        self.conditions.push(condition);
+
+        self
+    }
+
+    /// Adds a new condition according to which a given column should be
+    /// equal to any of the provided objects.
+    ///
+    /// Under the hood, each object is converted to a ZephyrVal and is later
+    /// serialized.
+    pub fn column_in<T: Serialize + TryInto<ZephyrVal>>(
+        &mut self,
+        column: impl ToString,
+        values: impl IntoIterator<Item = T>,
+    ) -> &mut Self {
+        let arguments = values.into_iter().map(serialize_as_val).collect();
+        let condition = Condition::ColumnIn(column.to_string(), arguments);
+        self.conditions.push(condition);
+
+        self
+    }
+
+    /// Adds a new condition according to which a given column should be
+    /// greater than the matching object.
+    ///
+    /// Under the hood, the object is converted to a ZephyrVal and is later
+    /// serialized.
+    pub fn column_greater_than<T: Serialize + TryInto<ZephyrVal>>(
+        &mut self,
+        column: impl ToString,
+        argument: T,
+    ) -> &mut Self {
+        let condition =
+            Condition::ColumnGreaterThan(column.to_string(), serialize_as_val(argument));
+        self.conditions.push(condition);
+
+        self
+    }
+
+    /// Adds a new condition according to which a given column should be
+    /// less than the matching object.
+    ///
+    /// Under the hood, the object is converted to a ZephyrVal and is later
+    /// serialized.
+    pub fn column_less_than<T: Serialize + TryInto<ZephyrVal>>(
+        &mut self,
+        column: impl ToString,
+        argument: T,
+    ) -> &mut Self {
+        let condition = Condition::ColumnLessThan(column.to_string(), serialize_as_val(argument));
         self.conditions.push(condition);
 
         self
@@ -287,6 +646,28 @@ impl TableQueryWrapper {
         Ok(interact.update(&EnvClient::empty(), &self.conditions))
     }
 
+    /// Executes the query and returns the number of matching rows.
+    pub fn count<T: DatabaseInteract>(&self) -> Result<u64, SdkError> {
+        let env = EnvClient::empty();
+
+        if self.action != Action::Read {
+            return Err(SdkError::CountOnOtherAction);
+        }
+
+        Ok(T::count(&env, Some(&self.conditions)))
+    }
+
+    /// Deletes the rows matching the conditions from the table of `T`.
+    /// Note: should only be used when deleting from a table.
+    pub fn delete<T: DatabaseInteract>(&mut self) -> Result<(), SdkError> {
+        if self.action != Action::Delete {
+            return Err(SdkError::DeleteOnOtherAction);
+        }
+
+        T::delete(&EnvClient::empty(), &self.conditions);
+        Ok(())
+    }
+
     /// Executes the query and returns the results.
     pub fn read<T: DatabaseInteract>(&self) -> Result<Vec<T>, SdkError> {
         let env = EnvClient::empty();
@@ -295,7 +676,31 @@ impl TableQueryWrapper {
             return Err(SdkError::UpdateOnReadAction);
         }
 
-        Ok(T::read_to_rows(&env, Some(&self.conditions)))
+        Ok(T::read_to_rows_with_options(
+            &env,
+            Some(&self.conditions),
+            &self.options,
+        ))
+    }
+
+    /// Executes the query and returns the first result, if any.
+    pub fn read_one<T: DatabaseInteract>(&self) -> Result<Option<T>, SdkError> {
+        let env = EnvClient::empty();
+
+        if self.action != Action::Read {
+            return Err(SdkError::UpdateOnReadAction);
+        }
+
+        let options = ReadOptions {
+            limit: Some(1),
+            ..self.options.clone()
+        };
+
+        Ok(
+            T::read_to_rows_with_options(&env, Some(&self.conditions), &options)
+                .into_iter()
+                .next(),
+        )
     }
 }
 
@@ -306,10 +711,274 @@ pub trait DatabaseInteract {
     where
         Self: Sized;
 
+    /// Reads from the database into a vector of `Self` applying the
+    /// provided limit, offset and ordering.
+    ///
+    /// The default implementation reads all the matching rows with
+    /// [`DatabaseInteract::read_to_rows`] and applies the offset and limit
+    /// in the program. It panics if an ordering is requested.
+    fn read_to_rows_with_options(
+        env: &EnvClient,
+        conditions: Option<&[Condition]>,
+        options: &ReadOptions,
+    ) -> Vec<Self>
+    where
+        Self: Sized,
+    {
+        assert!(
+            options.order_by.is_none(),
+            "Ordering requires read_to_rows_with_options, which DatabaseDerive generates."
+        );
+
+        Self::read_to_rows(env, conditions)
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+
+    /// Reads from the table of the program with the provided external id
+    /// into a vector of `Self`.
+    fn read_to_rows_external(
+        env: &EnvClient,
+        external_id: i64,
+        conditions: Option<&[Condition]>,
+    ) -> Vec<Self>
+    where
+        Self: Sized;
+
+    /// Deserializes rows read with the columns of `Self`.
+    fn from_rows(env: &EnvClient, rows: TableRows) -> Vec<Self>
+    where
+        Self: Sized;
+
+    /// Counts the rows of the database table matching the
+    /// provided conditions.
+    ///
+    /// The default implementation reads the matching rows with
+    /// [`DatabaseInteract::read_to_rows`] and counts them.
+    fn count(env: &EnvClient, conditions: Option<&[Condition]>) -> u64
+    where
+        Self: Sized,
+    {
+        Self::read_to_rows(env, conditions).len() as u64
+    }
+
     /// Inserts a row `Self` into the database table.
     fn put(&self, env: &EnvClient);
 
     /// Updates an existing row with `Self` into the database table
     /// using the provided conditions as update filter.
     fn update(&self, env: &EnvClient, conditions: &[Condition]);
+
+    /// Inserts a row `Self` into the database table, or updates the
+    /// existing row with the same values for `conflict_columns`.
+    fn upsert(&self, env: &EnvClient, conflict_columns: &[&str]);
+
+    /// Deletes the rows matching the provided conditions from the
+    /// database table.
+    fn delete(env: &EnvClient, conditions: &[Condition])
+    where
+        Self: Sized;
+}
+
+#[cfg(test)]
+mod test {
+    use rs_zephyr_common::ZephyrVal;
+    use soroban_sdk::xdr::{Limits, ScVal, WriteXdr};
+
+    use super::{Condition, TableRow, TableRows, TypeWrap};
+    use crate::{prelude::*, DatabaseDerive, EnvClient};
+
+    #[derive(DatabaseDerive, Default)]
+    #[with_name("transfers")]
+    struct Transfer {
+        #[zephyr(primary_key)]
+        hash: String,
+        #[zephyr(index)]
+        ledger: i64,
+        amount: i128,
+    }
+
+    // Only XDR columns, decoding ZephyrVal columns logs through the host on
+    // mismatches.
+    #[derive(DatabaseDerive)]
+    #[with_name("balances")]
+    struct Balance {
+        address: ScVal,
+        balance: ScVal,
+    }
+
+    #[test]
+    pub fn derived_schema() {
+        let ddl = "CREATE TABLE IF NOT EXISTS transfers (hash BYTEA PRIMARY KEY, ledger BYTEA, amount BYTEA);\n\
+                   CREATE INDEX IF NOT EXISTS transfers_ledger_idx ON transfers (ledger);";
+        assert_eq!(Transfer::ddl(), ddl);
+        assert_eq!(Transfer::create_table_sql(), ddl);
+
+        let table = Transfer::zephyr_toml_table();
+        assert_eq!(table.name, "transfers");
+        let columns: Vec<_> = table
+            .columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name.as_str(),
+                    column.col_type.as_str(),
+                    column.primary,
+                    column.index,
+                )
+            })
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("hash", "BYTEA", Some(true), None),
+                ("ledger", "BYTEA", None, Some(true)),
+                ("amount", "BYTEA", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn condition_parts() {
+        let column = "status".to_string();
+        let value = vec![1];
+        let conditions = [
+            Condition::ColumnEqualTo(column.clone(), value.clone()),
+            Condition::ColumnGreaterThan(column.clone(), value.clone()),
+            Condition::ColumnLessThan(column.clone(), value.clone()),
+            Condition::ColumnNotEqualTo(column.clone(), value.clone()),
+        ];
+
+        for (code, condition) in conditions.iter().enumerate() {
+            let expected: Vec<&[u8]> = vec![&value];
+            assert_eq!(condition.to_parts(), (&column, code as i64, expected));
+        }
+
+        let values = vec![vec![1], vec![2, 3]];
+        let condition = Condition::ColumnIn(column.clone(), values.clone());
+        let expected: Vec<&[u8]> = vec![&values[0], &values[1]];
+        assert_eq!(condition.to_parts(), (&column, 4, expected));
+    }
+
+    #[test]
+    pub fn condition_json_roundtrip() {
+        let conditions = [
+            (
+                Condition::ColumnEqualTo("hash".into(), vec![1, 2]),
+                r#"{"ColumnEqualTo":["hash",[1,2]]}"#,
+            ),
+            (
+                Condition::ColumnIn("hash".into(), vec![vec![1], vec![2, 3]]),
+                r#"{"ColumnIn":["hash",[[1],[2,3]]]}"#,
+            ),
+            (
+                Condition::ColumnGreaterThan("ledger".into(), vec![4]),
+                r#"{"ColumnGreaterThan":["ledger",[4]]}"#,
+            ),
+            (
+                Condition::ColumnLessThan("ledger".into(), vec![5]),
+                r#"{"ColumnLessThan":["ledger",[5]]}"#,
+            ),
+            (
+                Condition::ColumnNotEqualTo("status".into(), vec![]),
+                r#"{"ColumnNotEqualTo":["status",[]]}"#,
+            ),
+        ];
+
+        for (condition, json) in conditions {
+            assert_eq!(serde_json::to_string(&condition).unwrap(), json);
+            assert_eq!(Condition::from_json(json).unwrap(), condition);
+        }
+
+        assert!(Condition::from_json(r#"{"ColumnLike":["hash",[1]]}"#).is_err());
+        assert!(Condition::from_json(r#"{"ColumnEqualTo":["hash"]}"#).is_err());
+    }
+
+    fn zephyr_column(value: impl Into<ZephyrVal>) -> TypeWrap {
+        TypeWrap(bincode::serialize(&value.into()).unwrap())
+    }
+
+    #[test]
+    pub fn row_columns() {
+        let scval = ScVal::U32(7);
+        let row = TableRow {
+            row: vec![
+                zephyr_column("alice".to_string()),
+                TypeWrap(scval.to_xdr(Limits::none()).unwrap()),
+            ],
+        };
+
+        assert_eq!(
+            row.get_column::<ZephyrVal>(0).unwrap(),
+            ZephyrVal::String("alice".into())
+        );
+        assert_eq!(row.get_column_xdr::<ScVal>(1).unwrap(), scval);
+
+        assert!(row.get_column::<ZephyrVal>(2).is_err());
+        assert!(row.get_column_xdr::<ScVal>(2).is_err());
+        assert!(row.get_column_xdr::<ScVal>(0).is_err());
+    }
+
+    fn balance_row(address: u32, balance: u32) -> TableRow {
+        TableRow {
+            row: vec![
+                TypeWrap(ScVal::U32(address).to_xdr(Limits::none()).unwrap()),
+                TypeWrap(ScVal::U32(balance).to_xdr(Limits::none()).unwrap()),
+            ],
+        }
+    }
+
+    #[test]
+    pub fn typed_rows() {
+        let empty = TableRows { rows: vec![] };
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+
+        let rows = TableRows {
+            rows: vec![balance_row(1, 10), balance_row(2, 20)],
+        };
+        assert_eq!(rows.len(), 2);
+        assert!(!rows.is_empty());
+
+        assert_eq!((&rows).into_iter().count(), 2);
+        let addresses: Vec<ScVal> = rows
+            .clone()
+            .into_iter()
+            .map(|row| row.get_column_xdr(0).unwrap())
+            .collect();
+        assert_eq!(addresses, vec![ScVal::U32(1), ScVal::U32(2)]);
+
+        let balances: Vec<Balance> = rows.into_typed(&EnvClient::empty());
+        let balances: Vec<_> = balances
+            .into_iter()
+            .map(|balance| (balance.address, balance.balance))
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                (ScVal::U32(1), ScVal::U32(10)),
+                (ScVal::U32(2), ScVal::U32(20)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn column_conversions() {
+        assert_eq!(zephyr_column(1.5_f64).to_f64(), 1.5);
+        assert_eq!(zephyr_column(-7_i32).to_i32(), -7);
+        assert_eq!(zephyr_column(7_u32).to_u32(), 7);
+        assert!(zephyr_column(true).to_bool());
+        assert_eq!(zephyr_column("alice".to_string()).to_string(), "alice");
+
+        assert_eq!(TypeWrap(42_i128.to_be_bytes().to_vec()).to_i128(), 42);
+        assert_eq!(TypeWrap(42_u64.to_be_bytes().to_vec()).to_u64(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot read column as u32")]
+    pub fn column_conversion_mismatch() {
+        zephyr_column(-7_i32).to_u32();
+    }
 }
@@ -13,13 +13,13 @@ use soroban_sdk::{
 };
 
 use crate::{
-    database::{Database, DatabaseInteract, TableQueryWrapper},
+    database::{Database, DatabaseInteract, ReadOptions, TableQueryWrapper},
     external::{
         self, conclude_host, read_ledger_meta, scval_to_valid_host_val, soroban_simulate_tx,
         tx_send_message,
     },
     logger::EnvLogger,
-    Condition, MetaReader, SdkError, TableRows,
+    utils, Condition, MetaReader, PrettyContractEvent, SdkError, TableRows,
 };
 
 /// Zephyr's host environment client.
@@ -102,6 +102,17 @@ impl EnvClient {
         T::try_from_val(&self.soroban(), &val).map_err(|_| SdkError::Conversion)
     }
 
+    /// Returns the SHA-256 digest of the provided bytes.
+    /// See [`crate::utils::sha256`].
+    pub fn sha256(data: &[u8]) -> [u8; 32] {
+        utils::sha256(data)
+    }
+
+    /// Returns the SHA-256 digest of the provided string.
+    pub fn sha256_str(data: &str) -> [u8; 32] {
+        utils::sha256(data.as_bytes())
+    }
+
     pub(crate) fn message_relay(message: impl Serialize) {
         let serialized = bincode::serialize(&message).unwrap();
 
@@ -111,6 +122,9 @@ impl EnvClient {
     }
 
     /// Sends a web request message requests to the host.
+    ///
+    /// The request's `timeout_ms` and `retry` settings are relayed to
+    /// the host along with the request. Hosts that predate them ignore them.
     pub fn send_web_request(&self, request: AgnosticRequest) {
         let message = RelayedMessageRequest::Http(request);
 
@@ -126,6 +140,25 @@ impl EnvClient {
         T::read_to_rows(&self, None)
     }
 
+    /// Reads the first row of a database table, if any.
+    ///
+    /// Useful for tables holding a single row, only the first
+    /// row is requested to the host.
+    pub fn read_one<T: DatabaseInteract>(&self) -> Option<T> {
+        T::read_to_rows_with_options(self, None, &ReadOptions::first_row())
+            .into_iter()
+            .next()
+    }
+
+    /// Reads a database table that belongs to the program with the
+    /// provided external id.
+    ///
+    /// Unlike the `#[external]` attribute of the `DatabaseDerive` macro,
+    /// the id can be known only at runtime.
+    pub fn read_with_external<T: DatabaseInteract>(&self, external_id: i64) -> Vec<T> {
+        T::read_to_rows_external(self, external_id, None)
+    }
+
     /// Read a database table applying a column-level filter.
     /// Returns a filter object.
     pub fn read_filter(&self) -> TableQueryWrapper {
@@ -159,6 +192,14 @@ impl EnvClient {
         row.update(&self, conditions)
     }
 
+    /// Deletes rows from a database table.
+    ///
+    /// Returns a filter object whose conditions select the
+    /// rows to delete.
+    pub fn delete_filter(&self) -> TableQueryWrapper {
+        TableQueryWrapper::new(crate::database::Action::Delete)
+    }
+
     /// Raw function to write to the database a row.
     pub fn db_write(
         &self,
@@ -180,6 +221,33 @@ impl EnvClient {
         Database::update_table(table_name, columns, segments, conditions)
     }
 
+    /// Raw function to insert a row, or update the existing row with the
+    /// same values for `conflict_columns`.
+    pub fn db_upsert(
+        &self,
+        table_name: &str,
+        columns: &[&str],
+        segments: &[&[u8]],
+        conflict_columns: &[&str],
+    ) -> Result<(), SdkError> {
+        Database::upsert_table(table_name, columns, segments, conflict_columns)
+    }
+
+    /// Raw function to count the database rows matching the conditions.
+    pub fn db_count(
+        &self,
+        table_name: &str,
+        columns: &[&str],
+        conditions: Option<&[Condition]>,
+    ) -> Result<u64, SdkError> {
+        Database::count_table(table_name, columns, conditions)
+    }
+
+    /// Raw function to delete database rows.
+    pub fn db_delete(&self, table_name: &str, conditions: &[Condition]) -> Result<(), SdkError> {
+        Database::delete_table(table_name, conditions)
+    }
+
     /// Raw function to read from database.
     pub fn db_read(
         &self,
@@ -188,7 +256,20 @@ impl EnvClient {
         external: Option<i64>,
         conditions: Option<&[Condition]>,
     ) -> Result<TableRows, SdkError> {
-        Database::read_table(table_name, columns, external, conditions)
+        Database::read_table(table_name, columns, external, conditions, None)
+    }
+
+    /// Raw function to read from database applying a limit, offset
+    /// and ordering.
+    pub fn db_read_with_options(
+        &self,
+        table_name: &str,
+        columns: &[&str],
+        external: Option<i64>,
+        conditions: Option<&[Condition]>,
+        options: &ReadOptions,
+    ) -> Result<TableRows, SdkError> {
+        Database::read_table(table_name, columns, external, conditions, Some(options))
     }
 
     /// Returns the XDR reader object.
@@ -202,6 +283,43 @@ impl EnvClient {
         }
     }
 
+    /// Returns the events emitted by `contract` in the ledger being
+    /// processed, with the first topic decoded as `TopicType` and the
+    /// data as `DataType`.
+    ///
+    /// Events that can't be decoded into the provided types are skipped.
+    pub fn decode_contract_events_for<TopicType, DataType>(
+        &self,
+        contract: [u8; 32],
+    ) -> Vec<(TopicType, DataType)>
+    where
+        TopicType: soroban_sdk::TryFromVal<soroban_sdk::Env, soroban_sdk::Val>,
+        DataType: soroban_sdk::TryFromVal<soroban_sdk::Env, soroban_sdk::Val>,
+    {
+        decode_events_for(
+            self.reader().pretty().soroban_events(),
+            contract,
+            |topic| self.try_from_scval(topic).ok(),
+            |data| self.try_from_scval(data).ok(),
+        )
+    }
+
+    /// Returns the sequence of the ledger being processed, or `None`
+    /// when the client holds no ledger meta (e.g. [`EnvClient::empty`]).
+    pub fn ledger_sequence(&self) -> Option<u32> {
+        self.xdr
+            .as_ref()
+            .map(|meta| MetaReader::new(meta).ledger_sequence())
+    }
+
+    /// Returns the close time of the ledger being processed, or `None`
+    /// when the client holds no ledger meta (e.g. [`EnvClient::empty`]).
+    pub fn ledger_timestamp(&self) -> Option<u64> {
+        self.xdr
+            .as_ref()
+            .map(|meta| MetaReader::new(meta).ledger_timestamp())
+    }
+
     /// New instance of the zephyr client with the ledger
     /// meta already set.
     pub fn new() -> Self {
@@ -293,6 +411,11 @@ impl EnvClient {
     }
 
     /// Wrapper around self.simulate. This is a simpler SDK handler which
+    /// builds the contract invocation from the contract id, the function
+    /// name and its arguments, and takes the source account as a strkey.
+    ///
+    /// Returns [`SdkError::SimulationFailed`] with the error XDR when the
+    /// invocation fails.
     pub fn simulate_contract_call(
         &self,
         source: String,
@@ -303,13 +426,23 @@ impl EnvClient {
         let source_bytes = stellar_strkey::ed25519::PublicKey::from_string(&source)
             .unwrap()
             .0;
-        self.simulate(
-            source_bytes,
-            self.get_host_function(contract, fname, args),
-        )
+        let simulation =
+            self.simulate(source_bytes, self.get_host_function(contract, fname, args))?;
+
+        if let Err(error) = &simulation.invoke_result {
+            let xdr = error
+                .to_xdr_base64(Limits::none())
+                .map_err(|_| SdkError::Conversion)?;
+            return Err(SdkError::SimulationFailed(xdr));
+        }
+
+        Ok(simulation)
     }
 
     /// Wrapper around self.simulate. This is a simpler SDK handler which
+    /// simulates the contract invocation and returns the base64 XDR of the
+    /// unsigned transaction envelope, or the simulation error, in the
+    /// [`TransactionResponse`].
     pub fn simulate_contract_call_to_tx(
         &self,
         source: String,
@@ -449,6 +582,13 @@ pub struct InvokeHostFunctionSimulationResult {
     pub modified_entries: Vec<LedgerEntryDiff>,
 }
 
+impl InvokeHostFunctionSimulationResult {
+    /// Whether the invoked function returned successfully.
+    pub fn is_success(&self) -> bool {
+        self.invoke_result.is_ok()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SimulationResults {
     pub xdr: String,
@@ -519,3 +659,72 @@ pub struct TransactionResponse {
     pub tx: Option<String>,
     pub error: Option<String>,
 }
+
+/// Decodes the first topic and the data of the events emitted by `contract`,
+/// skipping the events that can't be decoded.
+fn decode_events_for<TopicType, DataType>(
+    events: Vec<PrettyContractEvent>,
+    contract: [u8; 32],
+    topic: impl Fn(&ScVal) -> Option<TopicType>,
+    data: impl Fn(&ScVal) -> Option<DataType>,
+) -> Vec<(TopicType, DataType)> {
+    events
+        .into_iter()
+        .filter(|event| event.contract == contract)
+        .filter_map(|event| Some((topic(event.topics.first()?)?, data(&event.data)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::xdr::{
+        ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, ExtensionPoint, Hash,
+        ScVal,
+    };
+
+    use super::{decode_events_for, EnvClient};
+
+    fn event(contract: [u8; 32], topic: ScVal, data: ScVal) -> ContractEvent {
+        ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(Hash(contract)),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: vec![topic].try_into().unwrap(),
+                data,
+            }),
+        }
+    }
+
+    fn as_u32(val: &ScVal) -> Option<u32> {
+        match val {
+            ScVal::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    pub fn events_decoded_for_contract() {
+        let (own, other) = ([1; 32], [2; 32]);
+        let events = vec![
+            event(own, ScVal::U32(1), ScVal::U32(10)),
+            event(other, ScVal::U32(2), ScVal::U32(20)),
+            event(own, ScVal::Void, ScVal::U32(30)),
+            event(own, ScVal::U32(4), ScVal::U32(40)),
+        ];
+        let events = events.into_iter().map(Into::into).collect();
+
+        assert_eq!(
+            decode_events_for(events, own, as_u32, as_u32),
+            vec![(1, 10), (4, 40)]
+        );
+    }
+
+    #[test]
+    pub fn no_ledger_without_meta() {
+        let env = EnvClient::empty();
+
+        assert_eq!(env.ledger_sequence(), None);
+        assert_eq!(env.ledger_timestamp(), None);
+    }
+}
@@ -45,6 +45,54 @@ extern "C" {
         contract_part_4: i64,
     ) -> (i64, i64, i64);
 
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "read_batch_contract_entries"]
+    pub fn read_batch_contract_entries() -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)]
+    #[link_name = "read_contract_entries_paginated"]
+    pub fn read_contract_entries_paginated(
+        contract_part_1: i64,
+        contract_part_2: i64,
+        contract_part_3: i64,
+        contract_part_4: i64,
+        page_offset: i64,
+        page_size: i64,
+    ) -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)]
+    #[link_name = "read_contract_code"]
+    pub fn read_contract_code(
+        hash_part_1: i64,
+        hash_part_2: i64,
+        hash_part_3: i64,
+        hash_part_4: i64,
+    ) -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)]
+    #[link_name = "read_account_entry"]
+    pub fn read_account_entry(
+        account_part_1: i64,
+        account_part_2: i64,
+        account_part_3: i64,
+        account_part_4: i64,
+    ) -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)]
+    #[link_name = "read_trustline_entry"]
+    pub fn read_trustline_entry(
+        account_part_1: i64,
+        account_part_2: i64,
+        account_part_3: i64,
+        account_part_4: i64,
+        asset_offset: i64,
+        asset_size: i64,
+    ) -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)]
+    #[link_name = "read_offer_entry"]
+    pub fn read_offer_entry(offer_id: i64) -> (i64, i64, i64);
+
     #[allow(improper_ctypes)]
     #[link_name = "scval_to_valid_host_val"]
     pub fn scval_to_valid_host_val(offset: i64, size: i64) -> (i64, i64);
@@ -74,6 +122,14 @@ extern "C" {
     #[link_name = "read_as_id"]
     pub fn read_as_id(id: i64) -> (i64, i64, i64);
 
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "read_raw_paginated"]
+    pub fn read_raw_paginated() -> (i64, i64, i64);
+
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "read_as_id_paginated"]
+    pub fn read_as_id_paginated(id: i64) -> (i64, i64, i64);
+
     #[allow(improper_ctypes)] // we alllow as we enabled multi-value
     #[link_name = "write_raw"]
     pub fn write_raw() -> i64;
@@ -82,6 +138,18 @@ extern "C" {
     #[link_name = "update_raw"]
     pub fn update_raw() -> i64;
 
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "upsert_raw"]
+    pub fn upsert_raw() -> i64;
+
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "count_raw"]
+    pub fn count_raw() -> (i64, i64);
+
+    #[allow(improper_ctypes)] // we alllow as we enabled multi-value
+    #[link_name = "delete_raw"]
+    pub fn delete_raw() -> i64;
+
     #[allow(improper_ctypes)] // we alllow as we enabled multi-value
     #[link_name = "read_ledger_meta"]
     pub fn read_ledger_meta() -> (i64, i64);
@@ -3,15 +3,81 @@ use std::fmt::Debug;
 use crate::{
     env::EnvClient,
     external::{
+        env_push_stack, read_account_entry, read_batch_contract_entries, read_contract_code,
         read_contract_data_entry_by_contract_id_and_key, read_contract_entries_by_contract,
-        read_contract_entries_by_contract_to_env, read_contract_instance,
+        read_contract_entries_by_contract_to_env, read_contract_entries_paginated,
+        read_contract_instance, read_offer_entry, read_trustline_entry,
     },
-    ContractDataEntry, ContractDataEntryStellarXDR, SdkError,
+    ContractDataEntry, ContractDataEntryStellarXDR, OfferEntryStellarXDR, SdkError,
+    TrustlineEntryStellarXDR,
+};
+use rs_zephyr_common::{wrapping::WrappedMaxBytes, Account};
+use soroban_sdk::xdr::{
+    AccountId, AlphaNum12, AlphaNum4, AssetCode12, AssetCode4, LedgerEntry, LedgerEntryData,
+    Limits, OfferEntry, PublicKey, ReadXdr, ScVal, TrustLineAsset, TrustLineEntry, Uint256,
+    WriteXdr,
 };
-use rs_zephyr_common::wrapping::WrappedMaxBytes;
-use soroban_sdk::xdr::{LedgerEntryData, Limits, ScVal, WriteXdr};
 use soroban_sdk::{Map, TryFromVal, Val};
 
+/// Converts a ledger entry received from the host to the SDK's XDR types.
+fn to_sdk_entry(entry: stellar_xdr::next::LedgerEntry) -> Result<LedgerEntry, SdkError> {
+    use stellar_xdr::next::WriteXdr;
+
+    let bytes = entry
+        .to_xdr(stellar_xdr::next::Limits::none())
+        .map_err(|_| SdkError::Conversion)?;
+    LedgerEntry::from_xdr(bytes, Limits::none()).map_err(|_| SdkError::Conversion)
+}
+
+/// Returns the offset and the number of entries requested for a page. One
+/// extra entry is requested to know whether there is a next page.
+fn page_window(page_size: usize, page: usize) -> Result<(i64, i64), SdkError> {
+    if page_size == 0 {
+        return Err(SdkError::InvalidPagination);
+    }
+
+    let offset = page
+        .checked_mul(page_size)
+        .and_then(|offset| i64::try_from(offset).ok());
+    let limit = page_size
+        .checked_add(1)
+        .and_then(|limit| i64::try_from(limit).ok());
+
+    offset.zip(limit).ok_or(SdkError::InvalidPagination)
+}
+
+/// Parses a `native` or `CODE:ISSUER` asset string.
+fn trustline_asset(asset: &str) -> Result<TrustLineAsset, SdkError> {
+    if asset == "native" {
+        return Ok(TrustLineAsset::Native);
+    }
+
+    let (code, issuer) = asset.split_once(':').ok_or(SdkError::Conversion)?;
+    let issuer = stellar_strkey::ed25519::PublicKey::from_string(issuer)
+        .map_err(|_| SdkError::Conversion)?;
+    let issuer = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(issuer.0)));
+
+    match code.len() {
+        1..=4 => {
+            let mut asset_code = [0; 4];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Ok(TrustLineAsset::CreditAlphanum4(AlphaNum4 {
+                asset_code: AssetCode4(asset_code),
+                issuer,
+            }))
+        }
+        5..=12 => {
+            let mut asset_code = [0; 12];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Ok(TrustLineAsset::CreditAlphanum12(AlphaNum12 {
+                asset_code: AssetCode12(asset_code),
+                issuer,
+            }))
+        }
+        _ => Err(SdkError::Conversion),
+    }
+}
+
 impl EnvClient {
     fn express_and_deser_entry(
         status: i64,
@@ -142,6 +208,57 @@ impl EnvClient {
         Ok(Some(self.from_scval::<R>(&data.val)))
     }
 
+    /// Returns the requested entry object of a certain contract
+    /// from the host's ledger, or `R::default()` when the entry
+    /// doesn't exist.
+    pub fn read_contract_entry_by_key_or_default<
+        T: soroban_sdk::TryIntoVal<soroban_sdk::Env, soroban_sdk::Val>,
+        R: soroban_sdk::TryFromVal<soroban_sdk::Env, soroban_sdk::Val> + Default + Debug,
+    >(
+        &self,
+        contract: [u8; 32],
+        val: T,
+    ) -> Result<R, SdkError> {
+        Ok(self
+            .read_contract_entry_by_key(contract, val)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the requested entry object of a certain contract
+    /// from the host's ledger, or `fallback` when the entry
+    /// doesn't exist.
+    pub fn read_contract_entry_or<
+        T: soroban_sdk::TryIntoVal<soroban_sdk::Env, soroban_sdk::Val>,
+        R: soroban_sdk::TryFromVal<soroban_sdk::Env, soroban_sdk::Val> + Debug,
+    >(
+        &self,
+        contract: [u8; 32],
+        val: T,
+        fallback: R,
+    ) -> Result<R, SdkError> {
+        Ok(self
+            .read_contract_entry_by_key(contract, val)?
+            .unwrap_or(fallback))
+    }
+
+    /// Returns the requested entry object of a certain contract
+    /// from the host's ledger, or the result of `fallback` when the
+    /// entry doesn't exist.
+    pub fn read_contract_entry_by_key_or_else<
+        T: soroban_sdk::TryIntoVal<soroban_sdk::Env, soroban_sdk::Val>,
+        R: soroban_sdk::TryFromVal<soroban_sdk::Env, soroban_sdk::Val> + Debug,
+        F: FnOnce() -> R,
+    >(
+        &self,
+        contract: [u8; 32],
+        val: T,
+        fallback: F,
+    ) -> Result<R, SdkError> {
+        Ok(self
+            .read_contract_entry_by_key(contract, val)?
+            .unwrap_or_else(fallback))
+    }
+
     /// Returns all the entry objects of a certain contract
     /// from the host's ledger.
     pub fn read_contract_entries(
@@ -169,6 +286,218 @@ impl EnvClient {
         Ok(deser.iter().map(|entry| entry.clone().into()).collect())
     }
 
+    /// Returns the entry objects of a certain contract whose key
+    /// matches `predicate`.
+    ///
+    /// Note: the filtering happens in the guest, so all the entries of
+    /// the contract are still read from the host and deserialized. This
+    /// saves the program from handling unneeded entries but not the read
+    /// cost, prefer [`EnvClient::read_contract_entry_by_scvalkey`] when
+    /// the keys are known in advance.
+    pub fn read_contract_entries_filtered<F>(
+        &self,
+        contract: [u8; 32],
+        predicate: F,
+    ) -> Result<Vec<ContractDataEntry>, SdkError>
+    where
+        F: Fn(&ScVal) -> bool,
+    {
+        Ok(self
+            .read_contract_entries(contract)?
+            .into_iter()
+            .filter(|entry| predicate(&entry.key))
+            .collect())
+    }
+
+    /// Returns all the entry objects of each of the provided contracts
+    /// from the host's ledger in a single host call.
+    ///
+    /// The result is in the same order as `contracts`, contracts without
+    /// entries get an empty vector.
+    pub fn batch_read_contract_entries(
+        &self,
+        contracts: &[[u8; 32]],
+    ) -> Result<Vec<Vec<ContractDataEntry>>, SdkError> {
+        unsafe {
+            env_push_stack(contracts.len() as i64);
+            for contract in contracts {
+                for part in WrappedMaxBytes::array_to_max_parts::<4>(contract) {
+                    env_push_stack(part)
+                }
+            }
+        }
+
+        let (status, offset, size) = unsafe { read_batch_contract_entries() };
+
+        SdkError::express_from_status(status)?;
+
+        let memory: *const u8 = offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, size as usize) };
+
+        let deser = bincode::deserialize::<Vec<Vec<ContractDataEntryStellarXDR>>>(slice)
+            .map_err(|_| SdkError::Conversion)?;
+        if deser.len() != contracts.len() {
+            return Err(SdkError::Conversion);
+        }
+
+        Ok(deser
+            .into_iter()
+            .map(|entries| entries.into_iter().map(|entry| entry.into()).collect())
+            .collect())
+    }
+
+    /// Returns a page of the entry objects of a certain contract
+    /// from the host's ledger, along with whether more pages exist.
+    ///
+    /// Pages are zero-indexed, so the first `page_size` entries are
+    /// returned by page `0`. Errors with [`SdkError::InvalidPagination`]
+    /// when `page_size` is zero or the page is out of range.
+    pub fn read_all_contract_entries_paginated(
+        &self,
+        contract: [u8; 32],
+        page_size: usize,
+        page: usize,
+    ) -> Result<(Vec<ContractDataEntry>, bool), SdkError> {
+        let contract_parts = WrappedMaxBytes::array_to_max_parts::<4>(&contract);
+        let (offset, limit) = page_window(page_size, page)?;
+
+        let (status, offset, size) = unsafe {
+            read_contract_entries_paginated(
+                contract_parts[0],
+                contract_parts[1],
+                contract_parts[2],
+                contract_parts[3],
+                offset,
+                limit,
+            )
+        };
+
+        SdkError::express_from_status(status)?;
+
+        let memory: *const u8 = offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, size as usize) };
+
+        let mut deser = bincode::deserialize::<Vec<ContractDataEntryStellarXDR>>(slice)
+            .map_err(|_| SdkError::Conversion)?;
+        let has_more = deser.len() > page_size;
+        deser.truncate(page_size);
+
+        Ok((
+            deser.into_iter().map(|entry| entry.into()).collect(),
+            has_more,
+        ))
+    }
+
+    /// Returns the WASM bytecode with the provided hash from the
+    /// host's ledger.
+    ///
+    /// The host returns no bytes when the code entry doesn't exist.
+    pub fn read_contract_wasm(&self, wasm_hash: [u8; 32]) -> Result<Option<Vec<u8>>, SdkError> {
+        let hash_parts = WrappedMaxBytes::array_to_max_parts::<4>(&wasm_hash);
+        let (status, offset, size) = unsafe {
+            read_contract_code(hash_parts[0], hash_parts[1], hash_parts[2], hash_parts[3])
+        };
+
+        SdkError::express_from_status(status)?;
+
+        if size == 0 {
+            return Ok(None);
+        }
+
+        let memory: *const u8 = offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, size as usize) };
+
+        Ok(Some(slice.to_vec()))
+    }
+
+    /// Returns the account with the provided `G...` strkey from the
+    /// host's ledger.
+    pub fn read_account_from_ledger(&self, account_id: &str) -> Result<Option<Account>, SdkError> {
+        let account = stellar_strkey::ed25519::PublicKey::from_string(account_id)
+            .map_err(|_| SdkError::Conversion)?
+            .0;
+
+        let account_parts = WrappedMaxBytes::array_to_max_parts::<4>(&account);
+        let (status, offset, size) = unsafe {
+            read_account_entry(
+                account_parts[0],
+                account_parts[1],
+                account_parts[2],
+                account_parts[3],
+            )
+        };
+
+        SdkError::express_from_status(status)?;
+
+        let memory: *const u8 = offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, size as usize) };
+
+        bincode::deserialize::<Option<Account>>(slice).map_err(|_| SdkError::Conversion)
+    }
+
+    /// Returns the trustline of an account (`G...` strkey) for an asset
+    /// (`native` or `CODE:ISSUER`) from the host's ledger.
+    pub fn read_trustline(
+        &self,
+        account_id: &str,
+        asset: &str,
+    ) -> Result<Option<TrustLineEntry>, SdkError> {
+        let account = stellar_strkey::ed25519::PublicKey::from_string(account_id)
+            .map_err(|_| SdkError::Conversion)?
+            .0;
+        let asset_bytes = trustline_asset(asset)?.to_xdr(Limits::none()).unwrap();
+        let (offset, size) = (asset_bytes.as_ptr() as i64, asset_bytes.len() as i64);
+
+        let account_parts = WrappedMaxBytes::array_to_max_parts::<4>(&account);
+        let (status, inbound_offset, inbound_size) = unsafe {
+            read_trustline_entry(
+                account_parts[0],
+                account_parts[1],
+                account_parts[2],
+                account_parts[3],
+                offset,
+                size,
+            )
+        };
+
+        SdkError::express_from_status(status)?;
+
+        let memory: *const u8 = inbound_offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, inbound_size as usize) };
+
+        let deser = bincode::deserialize::<Option<TrustlineEntryStellarXDR>>(slice)
+            .map_err(|_| SdkError::Conversion)?;
+        let Some(trustline) = deser else {
+            return Ok(None);
+        };
+
+        match to_sdk_entry(trustline.entry)?.data {
+            LedgerEntryData::Trustline(entry) => Ok(Some(entry)),
+            _ => Err(SdkError::Conversion),
+        }
+    }
+
+    /// Returns the offer with the provided id from the host's ledger.
+    pub fn read_offer(&self, offer_id: u64) -> Result<Option<OfferEntry>, SdkError> {
+        let (status, offset, size) = unsafe { read_offer_entry(offer_id as i64) };
+
+        SdkError::express_from_status(status)?;
+
+        let memory: *const u8 = offset as *const u8;
+        let slice = unsafe { core::slice::from_raw_parts(memory, size as usize) };
+
+        let deser = bincode::deserialize::<Option<OfferEntryStellarXDR>>(slice)
+            .map_err(|_| SdkError::Conversion)?;
+        let Some(offer) = deser else {
+            return Ok(None);
+        };
+
+        match to_sdk_entry(offer.entry)?.data {
+            LedgerEntryData::Offer(entry) => Ok(Some(entry)),
+            _ => Err(SdkError::Conversion),
+        }
+    }
+
     /// Returns all the entry objects of a certain contract
     /// from the host's ledger. This function retuns an iteraror
     /// over Soroban host objects, and should be used along with the
@@ -193,3 +522,22 @@ impl EnvClient {
         Ok(map)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::page_window;
+    use crate::SdkError;
+
+    #[test]
+    pub fn page_windows() {
+        assert_eq!(page_window(10, 0).unwrap(), (0, 11));
+        assert_eq!(page_window(10, 3).unwrap(), (30, 11));
+
+        for (page_size, page) in [(0, 0), (0, 5), (usize::MAX, 2), (usize::MAX, 0)] {
+            assert!(matches!(
+                page_window(page_size, page),
+                Err(SdkError::InvalidPagination)
+            ));
+        }
+    }
+}
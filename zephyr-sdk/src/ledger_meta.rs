@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+
+use crate::{
+    utils::{self, MAINNET_NETWORK_ID, TESTNET_NETWORK_ID},
+    ContractDataEntry,
+};
 use soroban_sdk::xdr::{
-    ContractEvent, ContractEventBody, GeneralizedTransactionSet, LedgerCloseMeta, LedgerEntry,
-    LedgerEntryChange, LedgerKey, ScVal, TransactionEnvelope, TransactionMeta,
-    TransactionPhase, TransactionResultMeta, TransactionResultResult, TxSetComponent, VecM,
+    AccountId, Asset, ContractEvent, ContractEventBody, FeeBumpTransactionInnerTx,
+    GeneralizedTransactionSet, Hash, LedgerCloseMeta, LedgerEntry, LedgerEntryChange,
+    LedgerEntryData, LedgerKey, Limits, MuxedAccount, Operation, OperationBody, Preconditions,
+    PublicKey, ScAddress, ScVal, Transaction, TransactionEnvelope, TransactionExt, TransactionMeta,
+    TransactionPhase, TransactionResultMeta, TransactionResultResult, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TxSetComponent, VecM, WriteXdr,
 };
 
 /// Represents all of the entry changes that happened in the
@@ -15,16 +24,197 @@ pub struct EntryChanges {
     pub created: Vec<LedgerEntry>,
 }
 
+impl EntryChanges {
+    /// Keeps the entries matching `entry` and the removed keys matching `key`.
+    fn filter(
+        &self,
+        entry: impl Fn(&LedgerEntryData) -> bool,
+        key: impl Fn(&LedgerKey) -> bool,
+    ) -> EntryChanges {
+        let entries = |entries: &Vec<LedgerEntry>| {
+            entries.iter().filter(|e| entry(&e.data)).cloned().collect()
+        };
+
+        EntryChanges {
+            state: entries(&self.state),
+            removed: self.removed.iter().filter(|k| key(k)).cloned().collect(),
+            updated: entries(&self.updated),
+            created: entries(&self.created),
+        }
+    }
+
+    /// Keeps only the contract data entries of the provided contract.
+    pub fn filter_by_contract(&self, contract: [u8; 32]) -> EntryChanges {
+        let is_contract = |address: &ScAddress| match address {
+            ScAddress::Contract(hash) => hash.0 == contract,
+            _ => false,
+        };
+
+        self.filter(
+            |data| matches!(data, LedgerEntryData::ContractData(data) if is_contract(&data.contract)),
+            |key| matches!(key, LedgerKey::ContractData(key) if is_contract(&key.contract)),
+        )
+    }
+
+    /// Returns the created contract data entries.
+    pub fn created_contract_data(&self) -> Vec<ContractDataEntry> {
+        self.created
+            .iter()
+            .filter_map(contract_data_entry)
+            .collect()
+    }
+
+    /// Returns the updated contract data entries.
+    pub fn updated_contract_data(&self) -> Vec<ContractDataEntry> {
+        self.updated
+            .iter()
+            .filter_map(contract_data_entry)
+            .collect()
+    }
+}
+
+fn contract_data_entry(entry: &LedgerEntry) -> Option<ContractDataEntry> {
+    let LedgerEntryData::ContractData(data) = &entry.data else {
+        return None;
+    };
+
+    Some(ContractDataEntry {
+        contract_id: data.contract.clone(),
+        key: data.key.clone(),
+        entry: entry.clone(),
+        durability: data.durability as i32,
+        last_modified: entry.last_modified_ledger_seq as i32,
+    })
+}
+
+/// A classic payment between two accounts.
+#[derive(Clone, Debug)]
+pub struct PaymentTransfer {
+    /// Strkey of the sending account.
+    pub from: String,
+
+    /// Strkey of the receiving account.
+    pub to: String,
+
+    /// `native` or `CODE:ISSUER` for credit assets.
+    pub asset: String,
+
+    /// Amount in stroops.
+    pub amount: i64,
+}
+
+fn account_id_to_strkey(account: &AccountId) -> String {
+    let PublicKey::PublicKeyTypeEd25519(key) = &account.0;
+    stellar_strkey::ed25519::PublicKey(key.0).to_string()
+}
+
+fn muxed_to_strkey(account: &MuxedAccount) -> String {
+    match account {
+        MuxedAccount::Ed25519(key) => stellar_strkey::ed25519::PublicKey(key.0).to_string(),
+        MuxedAccount::MuxedEd25519(muxed) => stellar_strkey::ed25519::MuxedAccount {
+            ed25519: muxed.ed25519.0,
+            id: muxed.id,
+        }
+        .to_string(),
+    }
+}
+
+fn asset_to_string(asset: &Asset) -> String {
+    let code_to_string = |code: &[u8]| {
+        let end = code.iter().position(|b| *b == 0).unwrap_or(code.len());
+        String::from_utf8_lossy(&code[..end]).to_string()
+    };
+
+    match asset {
+        Asset::Native => "native".into(),
+        Asset::CreditAlphanum4(asset) => format!(
+            "{}:{}",
+            code_to_string(&asset.asset_code.0),
+            account_id_to_strkey(&asset.issuer)
+        ),
+        Asset::CreditAlphanum12(asset) => format!(
+            "{}:{}",
+            code_to_string(&asset.asset_code.0),
+            account_id_to_strkey(&asset.issuer)
+        ),
+    }
+}
+
+/// Returns the operations of the transaction (or fee bump inner transaction).
+fn envelope_operations(envelope: &TransactionEnvelope) -> &[Operation] {
+    match envelope {
+        TransactionEnvelope::TxV0(v0) => &v0.tx.operations,
+        TransactionEnvelope::Tx(v1) => &v1.tx.operations,
+        TransactionEnvelope::TxFeeBump(fee_bump) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &fee_bump.tx.inner_tx;
+            &inner.tx.operations
+        }
+    }
+}
+
+/// Returns the hash of the envelope's transaction on the network with the
+/// given id, i.e. the hash of its signature payload.
+fn transaction_hash(envelope: &TransactionEnvelope, network_id: [u8; 32]) -> [u8; 32] {
+    let tagged_transaction = match envelope {
+        // Legacy envelopes are hashed as the equivalent v1 transaction.
+        TransactionEnvelope::TxV0(v0) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(Transaction {
+                source_account: MuxedAccount::Ed25519(v0.tx.source_account_ed25519.clone()),
+                fee: v0.tx.fee,
+                seq_num: v0.tx.seq_num.clone(),
+                cond: match &v0.tx.time_bounds {
+                    Some(time_bounds) => Preconditions::Time(time_bounds.clone()),
+                    None => Preconditions::None,
+                },
+                memo: v0.tx.memo.clone(),
+                operations: v0.tx.operations.clone(),
+                ext: TransactionExt::V0,
+            })
+        }
+        TransactionEnvelope::Tx(v1) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(v1.tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(fee_bump) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(fee_bump.tx.clone())
+        }
+    };
+
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(network_id),
+        tagged_transaction,
+    };
+
+    utils::sha256(&payload.to_xdr(Limits::none()).unwrap())
+}
+
+/// Returns whether the transaction (or fee bump inner transaction)
+/// was applied successfully.
+fn is_successful(result: &TransactionResultMeta) -> bool {
+    matches!(
+        result.result.result.result,
+        TransactionResultResult::TxSuccess(_) | TransactionResultResult::TxFeeBumpInnerSuccess(_)
+    )
+}
+
 /// Ledger meta reader.
 ///
 /// Aids developers in dealing with raw XDR structures.
 ///
-pub struct MetaReader<'a>(&'a soroban_sdk::xdr::LedgerCloseMeta);
+pub struct MetaReader<'a>(&'a soroban_sdk::xdr::LedgerCloseMeta, Option<[u8; 32]>);
 
 #[allow(missing_docs)]
 impl<'a> MetaReader<'a> {
     pub fn new(meta: &'a LedgerCloseMeta) -> Self {
-        Self(meta)
+        Self(meta, None)
+    }
+
+    /// Sets the id of the network the ledger belongs to, i.e. the SHA-256
+    /// hash of its passphrase (see [`utils::sha256`]). Only needed to pair
+    /// transactions with their results on networks other than mainnet and
+    /// testnet.
+    pub fn with_network_id(mut self, network_id: [u8; 32]) -> Self {
+        self.1 = Some(network_id);
+        self
     }
 
     pub fn ledger_sequence(&self) -> u32 {
@@ -41,11 +231,32 @@ impl<'a> MetaReader<'a> {
         }
     }
 
+    /// Returns the base fee of the ledger in stroops.
+    pub fn base_fee(&self) -> u32 {
+        match &self.0 {
+            LedgerCloseMeta::V1(v1) => v1.ledger_header.header.base_fee,
+            LedgerCloseMeta::V0(v0) => v0.ledger_header.header.base_fee,
+        }
+    }
+
+    /// Returns the sum of the fees charged to the transactions
+    /// of the ledger in stroops.
+    pub fn total_fees(&self) -> u64 {
+        self.tx_processing()
+            .iter()
+            .map(|result| result.result.result.fee_charged as u64)
+            .sum()
+    }
+
     // todo: add handles for other entries.
 
     pub fn envelopes(&self) -> Vec<TransactionEnvelope> {
+        self.envelope_refs().into_iter().cloned().collect()
+    }
+
+    fn envelope_refs(&self) -> Vec<&TransactionEnvelope> {
         match &self.0 {
-            LedgerCloseMeta::V0(v0) => v0.tx_set.txs.to_vec(),
+            LedgerCloseMeta::V0(v0) => v0.tx_set.txs.iter().collect(),
             LedgerCloseMeta::V1(v1) => {
                 let phases = match &v1.tx_set {
                     GeneralizedTransactionSet::V1(v1) => &v1.phases,
@@ -60,11 +271,7 @@ impl<'a> MetaReader<'a> {
                                 match txset_component {
                                     TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
                                         txset_maybe_discounted_fee,
-                                    ) => {
-                                        for tx_envelope in txset_maybe_discounted_fee.txs.to_vec() {
-                                            envelopes.push(tx_envelope)
-                                        }
-                                    }
+                                    ) => envelopes.extend(txset_maybe_discounted_fee.txs.iter()),
                                 }
                             }
                         }
@@ -76,41 +283,156 @@ impl<'a> MetaReader<'a> {
         }
     }
 
+    /// Returns the envelopes paired with their result meta, in the order
+    /// the transactions were applied.
+    ///
+    /// The transaction set isn't sorted in apply order, so envelopes are
+    /// paired with the results by transaction hash, which depends on the
+    /// network. The network set with [`MetaReader::with_network_id`] is
+    /// used, mainnet and testnet are tried when none is set. When the hashes
+    /// don't match the network, the envelopes are paired in the order they
+    /// appear in, which may not be the apply order.
     pub fn envelopes_with_meta(&self) -> Vec<(&TransactionEnvelope, &TransactionResultMeta)> {
-        let mut composed = Vec::new();
+        let network_ids = match self.1 {
+            Some(network_id) => vec![network_id],
+            None => vec![MAINNET_NETWORK_ID, TESTNET_NETWORK_ID],
+        };
 
+        for network_id in network_ids {
+            if let Some(composed) = self.envelopes_by_hash(network_id) {
+                return composed;
+            }
+        }
+
+        self.envelope_refs()
+            .into_iter()
+            .zip(self.tx_processing_refs().iter())
+            .collect()
+    }
+
+    /// Pairs the envelopes with their results by transaction hash on the
+    /// network with id `network_id`, `None` when a result has no envelope.
+    fn envelopes_by_hash(
+        &self,
+        network_id: [u8; 32],
+    ) -> Option<Vec<(&TransactionEnvelope, &TransactionResultMeta)>> {
+        let by_hash: HashMap<[u8; 32], &TransactionEnvelope> = self
+            .envelope_refs()
+            .into_iter()
+            .map(|envelope| (transaction_hash(envelope, network_id), envelope))
+            .collect();
+
+        self.tx_processing_refs()
+            .iter()
+            .map(|txmeta| {
+                let envelope = by_hash.get(&txmeta.result.transaction_hash.0)?;
+                Some((*envelope, txmeta))
+            })
+            .collect()
+    }
+
+    fn tx_processing_refs(&self) -> &[TransactionResultMeta] {
         match &self.0 {
-            LedgerCloseMeta::V0(_) => (), // todo
-            LedgerCloseMeta::V1(v1) => {
-                let phases = match &v1.tx_set {
-                    GeneralizedTransactionSet::V1(v1) => &v1.phases,
-                };
+            LedgerCloseMeta::V0(v0) => &v0.tx_processing,
+            LedgerCloseMeta::V1(v1) => &v1.tx_processing,
+        }
+    }
 
-                for phase in phases.iter() {
-                    match phase {
-                        TransactionPhase::V0(v0) => {
-                            for txset_component in v0.iter() {
-                                match txset_component {
-                                    TxSetComponent::TxsetCompTxsMaybeDiscountedFee(
-                                        txset_maybe_discounted_fee,
-                                    ) => {
-                                        for (idx, tx_envelope) in
-                                            txset_maybe_discounted_fee.txs.iter().enumerate()
-                                        {
-                                            let txmeta = &v1.tx_processing[idx];
+    /// Returns the transactions that were applied successfully along
+    /// with their result meta.
+    pub fn successful_transactions(&self) -> Vec<(&TransactionEnvelope, TransactionResultMeta)> {
+        self.envelopes_with_meta()
+            .into_iter()
+            .filter(|(_, meta)| is_successful(meta))
+            .map(|(envelope, meta)| (envelope, meta.clone()))
+            .collect()
+    }
 
-                                            composed.push((tx_envelope, txmeta))
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+    /// Returns the transactions that failed along with their result meta.
+    pub fn failed_transactions(&self) -> Vec<(&TransactionEnvelope, TransactionResultMeta)> {
+        self.envelopes_with_meta()
+            .into_iter()
+            .filter(|(_, meta)| !is_successful(meta))
+            .map(|(envelope, meta)| (envelope, meta.clone()))
+            .collect()
+    }
+
+    /// Returns the classic (non-Soroban) operations of all transactions
+    /// paired with the result meta of their transaction.
+    pub fn classic_operations(&self) -> Vec<(OperationBody, TransactionResultMeta)> {
+        let mut operations = Vec::new();
+
+        for (envelope, meta) in self.envelopes_with_meta() {
+            for operation in envelope_operations(envelope) {
+                if !matches!(operation.body, OperationBody::InvokeHostFunction(_)) {
+                    operations.push((operation.body.clone(), meta.clone()))
                 }
             }
-        };
+        }
 
-        composed
+        operations
+    }
+
+    /// Returns the payments performed by successful transactions through
+    /// `Payment`, `PathPaymentStrictSend` and `PathPaymentStrictReceive`
+    /// operations.
+    ///
+    /// Path payments report the exact side of the payment: the sent amount
+    /// and asset for strict send, the received amount and asset for strict
+    /// receive.
+    pub fn classic_payment_transfers(&self) -> Vec<PaymentTransfer> {
+        let mut transfers = Vec::new();
+
+        for (envelope, meta) in self.envelopes_with_meta() {
+            if !is_successful(meta) {
+                continue;
+            }
+
+            let (tx_source, operations) = match envelope {
+                TransactionEnvelope::TxV0(v0) => (
+                    stellar_strkey::ed25519::PublicKey(v0.tx.source_account_ed25519.0).to_string(),
+                    &v0.tx.operations,
+                ),
+                TransactionEnvelope::Tx(v1) => {
+                    (muxed_to_strkey(&v1.tx.source_account), &v1.tx.operations)
+                }
+                TransactionEnvelope::TxFeeBump(fee_bump) => {
+                    let FeeBumpTransactionInnerTx::Tx(inner) = &fee_bump.tx.inner_tx;
+                    (
+                        muxed_to_strkey(&inner.tx.source_account),
+                        &inner.tx.operations,
+                    )
+                }
+            };
+
+            for operation in operations.iter() {
+                let from = operation
+                    .source_account
+                    .as_ref()
+                    .map(muxed_to_strkey)
+                    .unwrap_or(tx_source.clone());
+
+                let (to, asset, amount) = match &operation.body {
+                    OperationBody::Payment(op) => (&op.destination, &op.asset, op.amount),
+                    OperationBody::PathPaymentStrictSend(op) => {
+                        (&op.destination, &op.send_asset, op.send_amount)
+                    }
+                    OperationBody::PathPaymentStrictReceive(op) => {
+                        (&op.destination, &op.dest_asset, op.dest_amount)
+                    }
+                    _ => continue,
+                };
+
+                transfers.push(PaymentTransfer {
+                    from,
+                    to: muxed_to_strkey(to),
+                    asset: asset_to_string(asset),
+                    amount,
+                })
+            }
+        }
+
+        transfers
     }
 
     pub fn tx_processing(&self) -> Vec<TransactionResultMeta> {
@@ -120,6 +442,14 @@ impl<'a> MetaReader<'a> {
         }
     }
 
+    /// Returns the hashes of all transactions in the order they were applied.
+    pub fn tx_hash_list(&self) -> Vec<[u8; 32]> {
+        self.tx_processing()
+            .iter()
+            .map(|result| result.result.transaction_hash.0)
+            .collect()
+    }
+
     pub fn v1_success_ledger_entries(&self) -> EntryChanges {
         let mut state_entries = Vec::new();
         let mut removed_entries = Vec::new();
@@ -130,14 +460,7 @@ impl<'a> MetaReader<'a> {
             LedgerCloseMeta::V0(_) => (),
             LedgerCloseMeta::V1(v1) => {
                 for tx_processing in v1.tx_processing.iter() {
-                    let result = &tx_processing.result.result.result;
-                    let success = match result {
-                        TransactionResultResult::TxSuccess(_) => true,
-                        TransactionResultResult::TxFeeBumpInnerSuccess(_) => true,
-                        _ => false,
-                    };
-
-                    if success {
+                    if is_successful(tx_processing) {
                         match &tx_processing.tx_apply_processing {
                             TransactionMeta::V3(meta) => {
                                 let ops = &meta.operations;
@@ -223,6 +546,62 @@ impl<'a> MetaReader<'a> {
         }
     }
 
+    /// Returns the changes to account entries.
+    pub fn account_entry_changes(&self) -> EntryChanges {
+        self.v1_ledger_entries().filter(
+            |data| matches!(data, LedgerEntryData::Account(_)),
+            |key| matches!(key, LedgerKey::Account(_)),
+        )
+    }
+
+    /// Returns the changes to trustline entries.
+    pub fn trustline_entry_changes(&self) -> EntryChanges {
+        self.v1_ledger_entries().filter(
+            |data| matches!(data, LedgerEntryData::Trustline(_)),
+            |key| matches!(key, LedgerKey::Trustline(_)),
+        )
+    }
+
+    /// Returns the changes to offer entries.
+    pub fn offer_entry_changes(&self) -> EntryChanges {
+        self.v1_ledger_entries().filter(
+            |data| matches!(data, LedgerEntryData::Offer(_)),
+            |key| matches!(key, LedgerKey::Offer(_)),
+        )
+    }
+
+    /// Returns the ids of the contracts whose instance was created
+    /// in this ledger.
+    pub fn all_created_contract_ids(&self) -> Vec<[u8; 32]> {
+        self.v1_ledger_entries()
+            .created
+            .iter()
+            .filter_map(|entry| match &entry.data {
+                LedgerEntryData::ContractData(data) => match (&data.contract, &data.key) {
+                    (ScAddress::Contract(hash), ScVal::LedgerKeyContractInstance) => Some(hash.0),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the ids of the contracts whose instance was removed
+    /// in this ledger.
+    pub fn all_removed_contract_ids(&self) -> Vec<[u8; 32]> {
+        self.v1_ledger_entries()
+            .removed
+            .iter()
+            .filter_map(|key| match key {
+                LedgerKey::ContractData(data) => match (&data.contract, &data.key) {
+                    (ScAddress::Contract(hash), ScVal::LedgerKeyContractInstance) => Some(hash.0),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn soroban_events(&self) -> Vec<ContractEvent> {
         let mut events = Vec::new();
 
@@ -239,6 +618,30 @@ impl<'a> MetaReader<'a> {
         events
     }
 
+    /// Returns the events emitted by the contract with the provided id.
+    pub fn soroban_events_by_contract(&self, contract: [u8; 32]) -> Vec<ContractEvent> {
+        self.soroban_events()
+            .into_iter()
+            .filter(|event| {
+                event
+                    .contract_id
+                    .as_ref()
+                    .is_some_and(|id| id.0 == contract)
+            })
+            .collect()
+    }
+
+    /// Returns the events whose first topic is `topic`.
+    pub fn soroban_events_by_topic(&self, topic: &ScVal) -> Vec<ContractEvent> {
+        self.soroban_events()
+            .into_iter()
+            .filter(|event| {
+                let ContractEventBody::V0(body) = &event.body;
+                body.topics.first() == Some(topic)
+            })
+            .collect()
+    }
+
     pub fn pretty(&self) -> PrettyMetaReader {
         PrettyMetaReader { inner: self }
     }
@@ -253,6 +656,12 @@ pub struct PrettyContractEvent {
     /// Contract address that emitted the event.
     pub contract: [u8; 32],
 
+    /// Strkey (`C...`) of the contract that emitted the event.
+    pub contract_strkey: String,
+
+    /// Hash of the transaction that emitted the event, when known.
+    pub tx_hash: Option<[u8; 32]>,
+
     /// Contract event topics.
     pub topics: VecM<ScVal>,
     
@@ -263,15 +672,34 @@ pub struct PrettyContractEvent {
 impl From<ContractEvent> for PrettyContractEvent {
     fn from(value: ContractEvent) -> Self {
         let ContractEventBody::V0(event) = &value.body;
+        let contract = value.contract_id.as_ref().unwrap().0;
 
         Self {
-            contract: value.contract_id.as_ref().unwrap().0,
+            contract,
+            contract_strkey: stellar_strkey::Contract(contract).to_string(),
+            tx_hash: None,
             topics: event.topics.clone(),
             data: event.data.clone(),
             raw: value,
         }
     }
 }
+impl PrettyContractEvent {
+    /// Returns the strkey (`C...`) representation of the contract
+    /// that emitted the event.
+    pub fn contract_as_strkey(&self) -> String {
+        stellar_strkey::Contract(self.contract).to_string()
+    }
+
+    /// Returns whether the event was emitted by the provided strkey
+    /// contract. Invalid strkeys never match.
+    pub fn matches_contract(&self, contract_str: &str) -> bool {
+        stellar_strkey::Contract::from_string(contract_str)
+            .map(|contract| contract.0 == self.contract)
+            .unwrap_or(false)
+    }
+}
+
 pub struct PrettyMetaReader<'a> {
     inner: &'a MetaReader<'a>,
 }
@@ -293,7 +721,8 @@ impl<'a> PrettyMetaReader<'a> {
         events
     }
 
-    pub fn soroban_events_and_txhash(&self) -> Vec<(PrettyContractEvent, [u8; 32])> {
+    /// Returns all Soroban events with their `tx_hash` set.
+    pub fn soroban_events_and_txhash(&self) -> Vec<PrettyContractEvent> {
         let mut events = Vec::new();
         
         for result in self.inner.tx_processing() {
@@ -302,7 +731,9 @@ impl<'a> PrettyMetaReader<'a> {
             if let TransactionMeta::V3(v3) = &result.tx_apply_processing {
                 if let Some(soroban) = &v3.soroban_meta {
                     for event in soroban.events.iter() {
-                        events.push((event.clone().into(), txhash))
+                        let mut event: PrettyContractEvent = event.clone().into();
+                        event.tx_hash = Some(txhash);
+                        events.push(event)
                     }
                 }
             }
@@ -310,4 +741,667 @@ impl<'a> PrettyMetaReader<'a> {
 
         events
     }
+
+    /// Returns all Soroban events along with the hash of the transaction
+    /// that emitted them and whether that transaction was successful.
+    pub fn soroban_events_with_tx_result(&self) -> Vec<(PrettyContractEvent, [u8; 32], bool)> {
+        let mut events = Vec::new();
+
+        for result in self.inner.tx_processing() {
+            let txhash = result.result.transaction_hash.0;
+            let success = is_successful(&result);
+
+            if let TransactionMeta::V3(v3) = &result.tx_apply_processing {
+                if let Some(soroban) = &v3.soroban_meta {
+                    for event in soroban.events.iter() {
+                        let mut event: PrettyContractEvent = event.clone().into();
+                        event.tx_hash = Some(txhash);
+                        events.push((event, txhash, success))
+                    }
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Returns the Soroban events emitted by successful transactions.
+    pub fn successful_soroban_events(&self) -> Vec<PrettyContractEvent> {
+        self.soroban_events_with_tx_result()
+            .into_iter()
+            .filter_map(|(event, _, success)| if success { Some(event) } else { None })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{transaction_hash, EntryChanges, MetaReader, PrettyContractEvent};
+    use crate::utils::{sha256, TESTNET_NETWORK_ID};
+    use soroban_sdk::xdr::{
+        AccountEntry, AccountEntryExt, AccountId, AlphaNum4, Asset, AssetCode4,
+        ContractDataDurability, ContractDataEntry, ContractEvent, ContractEventBody,
+        ContractEventType, ContractEventV0, ExtensionPoint, GeneralizedTransactionSet, Hash,
+        HostFunction, InvokeHostFunctionOp, LedgerCloseMeta, LedgerCloseMetaV0, LedgerCloseMetaV1,
+        LedgerEntry, LedgerEntryChange, LedgerEntryChanges, LedgerEntryData, LedgerEntryExt,
+        LedgerHeader, LedgerHeaderExt, LedgerHeaderHistoryEntry, LedgerHeaderHistoryEntryExt,
+        LedgerKey, LedgerKeyAccount, LedgerKeyContractData, LedgerKeyOffer, LedgerKeyTrustLine,
+        Memo, MuxedAccount, Operation, OperationBody, OperationMeta, PathPaymentStrictReceiveOp,
+        PathPaymentStrictSendOp, PaymentOp, Preconditions, PublicKey, ScAddress, ScSymbol, ScVal,
+        SequenceNumber, SorobanTransactionMeta, StellarValue, StellarValueExt, String32,
+        Thresholds, TimePoint, Transaction, TransactionEnvelope, TransactionExt, TransactionMeta,
+        TransactionMetaV3, TransactionResult, TransactionResultExt, TransactionResultMeta,
+        TransactionResultPair, TransactionResultResult, TransactionSet, TransactionSetV1,
+        TransactionV0, TransactionV0Envelope, TransactionV0Ext, TransactionV1Envelope,
+        TrustLineAsset, Uint256, VecM,
+    };
+
+    fn contract_event(contract: [u8; 32]) -> ContractEvent {
+        ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(Hash(contract)),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: VecM::default(),
+                data: ScVal::Void,
+            }),
+        }
+    }
+
+    #[test]
+    pub fn contract_strkey_roundtrip() {
+        let contract: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let strkey = "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O";
+
+        let event: PrettyContractEvent = contract_event(contract).into();
+        assert_eq!(event.contract, contract);
+        assert_eq!(event.contract_strkey, strkey);
+        assert_eq!(event.contract_as_strkey(), strkey);
+
+        let decoded = stellar_strkey::Contract::from_string(&event.contract_as_strkey()).unwrap();
+        assert_eq!(decoded.0, contract);
+
+        assert!(event.matches_contract(strkey));
+        assert!(!event.matches_contract("CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4"));
+        assert!(!event.matches_contract("not a strkey"));
+    }
+
+    fn operations() -> VecM<Operation, 100> {
+        vec![Operation {
+            source_account: None,
+            body: OperationBody::Inflation,
+        }]
+        .try_into()
+        .unwrap()
+    }
+
+    fn result_meta(envelope: &TransactionEnvelope, success: bool) -> TransactionResultMeta {
+        result_meta_on(envelope, success, TESTNET_NETWORK_ID)
+    }
+
+    fn result_meta_on(
+        envelope: &TransactionEnvelope,
+        success: bool,
+        network_id: [u8; 32],
+    ) -> TransactionResultMeta {
+        TransactionResultMeta {
+            result: TransactionResultPair {
+                transaction_hash: Hash(transaction_hash(envelope, network_id)),
+                result: TransactionResult {
+                    fee_charged: 100,
+                    result: if success {
+                        TransactionResultResult::TxSuccess(VecM::default())
+                    } else {
+                        TransactionResultResult::TxFailed(VecM::default())
+                    },
+                    ext: TransactionResultExt::V0,
+                },
+            },
+            fee_processing: LedgerEntryChanges(VecM::default()),
+            tx_apply_processing: TransactionMeta::V0(VecM::default()),
+        }
+    }
+
+    fn ledger_header() -> LedgerHeaderHistoryEntry {
+        LedgerHeaderHistoryEntry {
+            hash: Hash([2; 32]),
+            header: LedgerHeader {
+                ledger_version: 19,
+                previous_ledger_hash: Hash([1; 32]),
+                scp_value: StellarValue {
+                    tx_set_hash: Hash([0; 32]),
+                    close_time: TimePoint(1_700_000_000),
+                    upgrades: VecM::default(),
+                    ext: StellarValueExt::Basic,
+                },
+                tx_set_result_hash: Hash([0; 32]),
+                bucket_list_hash: Hash([0; 32]),
+                ledger_seq: 2000,
+                total_coins: 1_000_000_000,
+                fee_pool: 0,
+                inflation_seq: 0,
+                id_pool: 0,
+                base_fee: 100,
+                base_reserve: 5_000_000,
+                max_tx_set_size: 100,
+                skip_list: core::array::from_fn(|_| Hash([0; 32])),
+                ext: LedgerHeaderExt::V0,
+            },
+            ext: LedgerHeaderHistoryEntryExt::V0,
+        }
+    }
+
+    fn v0_meta(
+        txs: Vec<TransactionEnvelope>,
+        tx_processing: Vec<TransactionResultMeta>,
+    ) -> LedgerCloseMeta {
+        LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+            ledger_header: ledger_header(),
+            tx_set: TransactionSet {
+                previous_ledger_hash: Hash([1; 32]),
+                txs: txs.try_into().unwrap(),
+            },
+            tx_processing: tx_processing.try_into().unwrap(),
+            upgrades_processing: VecM::default(),
+            scp_info: VecM::default(),
+        })
+    }
+
+    #[test]
+    pub fn legacy_envelope_hash() {
+        let legacy = TransactionEnvelope::TxV0(TransactionV0Envelope {
+            tx: TransactionV0 {
+                source_account_ed25519: Uint256([1; 32]),
+                fee: 100,
+                seq_num: SequenceNumber(7),
+                time_bounds: None,
+                memo: Memo::None,
+                operations: operations(),
+                ext: TransactionV0Ext::V0,
+            },
+            signatures: VecM::default(),
+        });
+        let v1 = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(7),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: operations(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        });
+
+        assert_eq!(
+            transaction_hash(&legacy, TESTNET_NETWORK_ID),
+            transaction_hash(&v1, TESTNET_NETWORK_ID)
+        );
+    }
+
+    fn envelope(seq_num: i64) -> TransactionEnvelope {
+        envelope_with(seq_num, operations())
+    }
+
+    fn envelope_with(seq_num: i64, operations: VecM<Operation, 100>) -> TransactionEnvelope {
+        TransactionEnvelope::TxV0(TransactionV0Envelope {
+            tx: TransactionV0 {
+                source_account_ed25519: Uint256([1; 32]),
+                fee: 100,
+                seq_num: SequenceNumber(seq_num),
+                time_bounds: None,
+                memo: Memo::None,
+                operations,
+                ext: TransactionV0Ext::V0,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    #[test]
+    pub fn envelopes_paired_by_hash() {
+        let (succeeded, failed) = (envelope(1), envelope(2));
+
+        // The transactions are applied in a different order than the set's.
+        let meta = v0_meta(
+            vec![succeeded.clone(), failed.clone()],
+            vec![result_meta(&failed, false), result_meta(&succeeded, true)],
+        );
+        let reader = MetaReader::new(&meta);
+
+        let paired = reader.envelopes_with_meta();
+        assert_eq!(paired.len(), 2);
+        assert_eq!(paired[0].0, &failed);
+        assert_eq!(paired[1].0, &succeeded);
+
+        let successful = reader.successful_transactions();
+        assert_eq!(successful.len(), 1);
+        assert_eq!(successful[0].0, &succeeded);
+
+        let failures = reader.failed_transactions();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, &failed);
+    }
+
+    #[test]
+    pub fn envelopes_paired_on_other_networks() {
+        let network_id = sha256(b"Standalone Network ; February 2017");
+        let (first, second) = (envelope(1), envelope(2));
+        let meta = v0_meta(
+            vec![first.clone(), second.clone()],
+            vec![
+                result_meta_on(&second, true, network_id),
+                result_meta_on(&first, true, network_id),
+            ],
+        );
+
+        // Without the network, the envelopes are paired by position.
+        let reader = MetaReader::new(&meta);
+        let paired = reader.envelopes_with_meta();
+        assert_eq!(paired[0].0, &first);
+        assert_eq!(paired[1].0, &second);
+
+        let reader = MetaReader::new(&meta).with_network_id(network_id);
+        let paired = reader.envelopes_with_meta();
+        assert_eq!(paired.len(), 2);
+        assert_eq!(paired[0].0, &second);
+        assert_eq!(paired[1].0, &first);
+    }
+
+    fn v1_meta(tx_processing: Vec<TransactionResultMeta>) -> LedgerCloseMeta {
+        LedgerCloseMeta::V1(LedgerCloseMetaV1 {
+            ext: ExtensionPoint::V0,
+            ledger_header: ledger_header(),
+            tx_set: GeneralizedTransactionSet::V1(TransactionSetV1 {
+                previous_ledger_hash: Hash([1; 32]),
+                phases: VecM::default(),
+            }),
+            tx_processing: tx_processing.try_into().unwrap(),
+            upgrades_processing: VecM::default(),
+            scp_info: VecM::default(),
+            total_byte_size_of_bucket_list: 0,
+            evicted_temporary_ledger_keys: VecM::default(),
+            evicted_persistent_ledger_entries: VecM::default(),
+        })
+    }
+
+    /// Result meta of a successful Soroban transaction.
+    fn v3_result_meta(
+        changes: Vec<LedgerEntryChange>,
+        events: Vec<ContractEvent>,
+    ) -> TransactionResultMeta {
+        let operation = OperationMeta {
+            changes: LedgerEntryChanges(changes.try_into().unwrap()),
+        };
+
+        TransactionResultMeta {
+            tx_apply_processing: TransactionMeta::V3(TransactionMetaV3 {
+                ext: ExtensionPoint::V0,
+                tx_changes_before: LedgerEntryChanges(VecM::default()),
+                operations: vec![operation].try_into().unwrap(),
+                tx_changes_after: LedgerEntryChanges(VecM::default()),
+                soroban_meta: Some(SorobanTransactionMeta {
+                    ext: ExtensionPoint::V0,
+                    events: events.try_into().unwrap(),
+                    return_value: ScVal::Void,
+                    diagnostic_events: VecM::default(),
+                }),
+            }),
+            ..result_meta(&envelope(1), true)
+        }
+    }
+
+    fn contract_data(contract: [u8; 32], key: ScVal) -> LedgerEntry {
+        LedgerEntry {
+            last_modified_ledger_seq: 2000,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                ext: ExtensionPoint::V0,
+                contract: ScAddress::Contract(Hash(contract)),
+                key,
+                durability: ContractDataDurability::Persistent,
+                val: ScVal::Void,
+            }),
+            ext: LedgerEntryExt::V0,
+        }
+    }
+
+    fn contract_data_key(contract: [u8; 32], key: ScVal) -> LedgerKey {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(Hash(contract)),
+            key,
+            durability: ContractDataDurability::Persistent,
+        })
+    }
+
+    #[test]
+    pub fn created_and_removed_contracts() {
+        let instance = ScVal::LedgerKeyContractInstance;
+        let changes = vec![
+            LedgerEntryChange::Created(contract_data([1; 32], instance.clone())),
+            LedgerEntryChange::Created(contract_data([2; 32], ScVal::U32(0))),
+            LedgerEntryChange::Updated(contract_data([3; 32], instance.clone())),
+            LedgerEntryChange::Removed(contract_data_key([4; 32], instance)),
+            LedgerEntryChange::Removed(contract_data_key([5; 32], ScVal::U32(0))),
+        ];
+        let meta = v1_meta(vec![v3_result_meta(changes, vec![])]);
+        let reader = MetaReader::new(&meta);
+
+        assert_eq!(reader.all_created_contract_ids(), vec![[1; 32]]);
+        assert_eq!(reader.all_removed_contract_ids(), vec![[4; 32]]);
+
+        let meta = v0_meta(vec![], vec![]);
+        assert!(MetaReader::new(&meta).all_created_contract_ids().is_empty());
+    }
+
+    fn account(key: u8) -> MuxedAccount {
+        MuxedAccount::Ed25519(Uint256([key; 32]))
+    }
+
+    fn strkey(key: u8) -> String {
+        stellar_strkey::ed25519::PublicKey([key; 32]).to_string()
+    }
+
+    #[test]
+    pub fn payment_transfers() {
+        let usdc = Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(*b"USDC"),
+            issuer: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([9; 32]))),
+        });
+        let payment = |destination: u8, amount: i64| {
+            OperationBody::Payment(PaymentOp {
+                destination: account(destination),
+                asset: Asset::Native,
+                amount,
+            })
+        };
+        let operations = vec![
+            Operation {
+                source_account: None,
+                body: payment(2, 50),
+            },
+            Operation {
+                source_account: Some(account(3)),
+                body: OperationBody::PathPaymentStrictSend(PathPaymentStrictSendOp {
+                    send_asset: usdc.clone(),
+                    send_amount: 10,
+                    destination: account(4),
+                    dest_asset: Asset::Native,
+                    dest_min: 90,
+                    path: VecM::default(),
+                }),
+            },
+            Operation {
+                source_account: None,
+                body: OperationBody::PathPaymentStrictReceive(PathPaymentStrictReceiveOp {
+                    send_asset: Asset::Native,
+                    send_max: 100,
+                    destination: account(5),
+                    dest_asset: usdc,
+                    dest_amount: 7,
+                    path: VecM::default(),
+                }),
+            },
+            Operation {
+                source_account: None,
+                body: OperationBody::Inflation,
+            },
+        ];
+        let succeeded = envelope_with(1, operations.try_into().unwrap());
+        let failed = envelope_with(
+            2,
+            vec![Operation {
+                source_account: None,
+                body: payment(6, 1),
+            }]
+            .try_into()
+            .unwrap(),
+        );
+        let meta = v0_meta(
+            vec![succeeded.clone(), failed.clone()],
+            vec![result_meta(&succeeded, true), result_meta(&failed, false)],
+        );
+
+        let transfers: Vec<_> = MetaReader::new(&meta)
+            .classic_payment_transfers()
+            .into_iter()
+            .map(|transfer| (transfer.from, transfer.to, transfer.asset, transfer.amount))
+            .collect();
+        let usdc = format!("USDC:{}", strkey(9));
+        assert_eq!(
+            transfers,
+            vec![
+                (strkey(1), strkey(2), "native".to_string(), 50),
+                (strkey(3), strkey(4), usdc.clone(), 10),
+                (strkey(1), strkey(5), usdc, 7),
+            ]
+        );
+    }
+
+    fn topic_event(contract: [u8; 32], topic: &str) -> ContractEvent {
+        let mut event = contract_event(contract);
+        let ContractEventBody::V0(body) = &mut event.body;
+        body.topics = vec![symbol(topic), ScVal::U32(0)].try_into().unwrap();
+        event
+    }
+
+    fn symbol(name: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+    }
+
+    #[test]
+    pub fn events_by_contract_and_topic() {
+        let events = vec![
+            topic_event([1; 32], "transfer"),
+            topic_event([2; 32], "mint"),
+            contract_event([1; 32]),
+        ];
+        let later = vec![topic_event([1; 32], "mint")];
+        let meta = v0_meta(
+            vec![],
+            vec![
+                v3_result_meta(vec![], events.clone()),
+                result_meta(&envelope(2), true),
+                v3_result_meta(vec![], later.clone()),
+            ],
+        );
+        let reader = MetaReader::new(&meta);
+
+        assert_eq!(reader.soroban_events().len(), 4);
+        assert_eq!(
+            reader.soroban_events_by_contract([1; 32]),
+            vec![events[0].clone(), events[2].clone(), later[0].clone()]
+        );
+        assert!(reader.soroban_events_by_contract([3; 32]).is_empty());
+
+        assert_eq!(
+            reader.soroban_events_by_topic(&symbol("mint")),
+            vec![events[1].clone(), later[0].clone()]
+        );
+        // Only the first topic is matched.
+        assert!(reader.soroban_events_by_topic(&ScVal::U32(0)).is_empty());
+    }
+
+    fn account_id(key: u8) -> AccountId {
+        AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([key; 32])))
+    }
+
+    fn account_entry(key: u8, balance: i64) -> LedgerEntry {
+        LedgerEntry {
+            last_modified_ledger_seq: 2000,
+            data: LedgerEntryData::Account(AccountEntry {
+                account_id: account_id(key),
+                balance,
+                seq_num: SequenceNumber(1),
+                num_sub_entries: 0,
+                inflation_dest: None,
+                flags: 0,
+                home_domain: String32::default(),
+                thresholds: Thresholds([1, 0, 0, 0]),
+                signers: VecM::default(),
+                ext: AccountEntryExt::V0,
+            }),
+            ext: LedgerEntryExt::V0,
+        }
+    }
+
+    #[test]
+    pub fn entry_changes_by_type() {
+        let trustline = LedgerKey::Trustline(LedgerKeyTrustLine {
+            account_id: account_id(1),
+            asset: TrustLineAsset::Native,
+        });
+        let offer = LedgerKey::Offer(LedgerKeyOffer {
+            seller_id: account_id(1),
+            offer_id: 7,
+        });
+        let changes = vec![
+            LedgerEntryChange::State(account_entry(1, 10)),
+            LedgerEntryChange::Updated(account_entry(1, 20)),
+            LedgerEntryChange::Removed(LedgerKey::Account(LedgerKeyAccount {
+                account_id: account_id(2),
+            })),
+            LedgerEntryChange::Removed(trustline.clone()),
+            LedgerEntryChange::Removed(offer.clone()),
+            LedgerEntryChange::Created(contract_data([1; 32], ScVal::U32(0))),
+        ];
+        let meta = v1_meta(vec![v3_result_meta(changes, vec![])]);
+        let reader = MetaReader::new(&meta);
+
+        let accounts = reader.account_entry_changes();
+        assert_eq!(accounts.state, vec![account_entry(1, 10)]);
+        assert_eq!(accounts.updated, vec![account_entry(1, 20)]);
+        assert_eq!(accounts.removed.len(), 1);
+        assert!(accounts.created.is_empty());
+
+        let trustlines = reader.trustline_entry_changes();
+        assert_eq!(trustlines.removed, vec![trustline]);
+        assert!(trustlines.state.is_empty() && trustlines.updated.is_empty());
+        assert!(trustlines.created.is_empty());
+
+        let offers = reader.offer_entry_changes();
+        assert_eq!(offers.removed, vec![offer]);
+        assert!(offers.state.is_empty() && offers.updated.is_empty());
+        assert!(offers.created.is_empty());
+    }
+
+    #[test]
+    pub fn ledger_fees() {
+        let (first, second) = (envelope(1), envelope(2));
+        let mut expensive = result_meta(&second, false);
+        expensive.result.result.fee_charged = 250;
+        let meta = v0_meta(
+            vec![first.clone(), second],
+            vec![result_meta(&first, true), expensive],
+        );
+        let reader = MetaReader::new(&meta);
+
+        assert_eq!(reader.base_fee(), 100);
+        assert_eq!(reader.total_fees(), 350);
+
+        let meta = v1_meta(vec![]);
+        let reader = MetaReader::new(&meta);
+        assert_eq!(reader.base_fee(), 100);
+        assert_eq!(reader.total_fees(), 0);
+    }
+
+    #[test]
+    pub fn classic_operations_only() {
+        let upload = OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::UploadContractWasm(Default::default()),
+            auth: VecM::default(),
+        });
+        let operations = [upload, OperationBody::Inflation]
+            .into_iter()
+            .map(|body| Operation {
+                source_account: None,
+                body,
+            })
+            .collect::<Vec<_>>();
+        let (soroban, classic) = (
+            envelope_with(1, operations.try_into().unwrap()),
+            envelope(2),
+        );
+        let meta = v0_meta(
+            vec![soroban.clone(), classic.clone()],
+            vec![result_meta(&soroban, true), result_meta(&classic, false)],
+        );
+
+        let operations = MetaReader::new(&meta).classic_operations();
+        assert_eq!(
+            operations,
+            vec![
+                (OperationBody::Inflation, result_meta(&soroban, true)),
+                (OperationBody::Inflation, result_meta(&classic, false)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn contract_data_changes() {
+        let (own, other) = ([1; 32], [2; 32]);
+        let changes = EntryChanges {
+            state: vec![contract_data(own, ScVal::U32(0)), account_entry(1, 10)],
+            removed: vec![
+                contract_data_key(own, ScVal::U32(1)),
+                contract_data_key(other, ScVal::U32(1)),
+            ],
+            updated: vec![
+                contract_data(other, ScVal::U32(2)),
+                contract_data(own, ScVal::U32(2)),
+            ],
+            created: vec![contract_data(own, ScVal::U32(3)), account_entry(2, 10)],
+        };
+
+        let filtered = changes.filter_by_contract(own);
+        assert_eq!(filtered.state, vec![contract_data(own, ScVal::U32(0))]);
+        assert_eq!(
+            filtered.removed,
+            vec![contract_data_key(own, ScVal::U32(1))]
+        );
+        assert_eq!(filtered.updated, vec![contract_data(own, ScVal::U32(2))]);
+        assert_eq!(filtered.created, vec![contract_data(own, ScVal::U32(3))]);
+
+        let created = changes.created_contract_data();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].contract_id, ScAddress::Contract(Hash(own)));
+        assert_eq!(created[0].key, ScVal::U32(3));
+        assert_eq!(created[0].entry, contract_data(own, ScVal::U32(3)));
+        assert_eq!(
+            created[0].durability,
+            ContractDataDurability::Persistent as i32
+        );
+        assert_eq!(created[0].last_modified, 2000);
+
+        let updated: Vec<_> = changes
+            .updated_contract_data()
+            .into_iter()
+            .map(|entry| (entry.contract_id, entry.key))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                (ScAddress::Contract(Hash(other)), ScVal::U32(2)),
+                (ScAddress::Contract(Hash(own)), ScVal::U32(2)),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn tx_hashes_in_apply_order() {
+        let (first, second) = (envelope(1), envelope(2));
+        let meta = v0_meta(
+            vec![first.clone(), second.clone()],
+            vec![result_meta(&second, true), result_meta(&first, false)],
+        );
+
+        assert_eq!(
+            MetaReader::new(&meta).tx_hash_list(),
+            vec![
+                transaction_hash(&second, TESTNET_NETWORK_ID),
+                transaction_hash(&first, TESTNET_NETWORK_ID),
+            ]
+        );
+        assert!(MetaReader::new(&v1_meta(vec![])).tx_hash_list().is_empty());
+    }
 }
@@ -54,12 +54,13 @@ mod external;
 mod ledger;
 mod ledger_meta;
 mod logger;
+pub mod protocols;
 mod symbol;
 pub mod utils;
 
 pub mod prelude;
 
-use rs_zephyr_common::ZephyrStatus;
+use rs_zephyr_common::{DatabaseError, ZephyrStatus};
 use serde::Deserialize;
 use serde::Serialize;
 use soroban_sdk::xdr::LedgerEntry;
@@ -70,9 +71,9 @@ use soroban_sdk::xdr::ScVal;
 use stellar_xdr::next::WriteXdr;
 use thiserror::Error;
 
-pub use database::{DatabaseInteract, TableRow, TableRows};
+pub use database::{DatabaseInteract, ReadOptions, TableRow, TableRows};
 pub use env::EnvClient;
-pub use ledger_meta::{MetaReader, PrettyMetaReader, PrettyContractEvent};
+pub use ledger_meta::{MetaReader, PaymentTransfer, PrettyMetaReader, PrettyContractEvent};
 pub use logger::EnvLogger;
 pub use ledger_meta::EntryChanges;
 pub use soroban_sdk;
@@ -81,7 +82,7 @@ pub use database::Condition;
 pub use macros::DatabaseInteract as DatabaseDerive;
 pub use rs_zephyr_common::{
     http::{AgnosticRequest, Method},
-    ZephyrVal,
+    Account, ZephyrVal,
 };
 
 fn to_fixed<T, const N: usize>(v: Vec<T>) -> [T; N] {
@@ -95,22 +96,24 @@ fn to_fixed<T, const N: usize>(v: Vec<T>) -> [T; N] {
 //static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 /// Zephyr SDK errors.
-#[derive(Clone, Debug, Copy, Error)]
+#[derive(Clone, Debug, Error)]
 #[allow(missing_docs)]
 pub enum SdkError {
-    #[error("Conversion error.")]
+    #[error("Conversion error. The data returned by the host doesn't match the expected type.")]
     Conversion,
 
-    #[error("Error in reading database.")]
+    #[error("Failed to read from database. Check that the table and column names exist and match the server schema.")]
     DbRead,
 
-    #[error("Error in writing database.")]
+    #[error("Failed to write to database. Check that the table and column names exist and that the values match the column types.")]
     DbWrite,
 
-    #[error("No value found on host pseudo store.")]
+    #[error("No value found on host pseudo store. The host expected more arguments than the SDK pushed.")]
     NoValOnStack,
 
-    #[error("Incorrect host configurations.")]
+    #[error(
+        "Host configuration error. Verify that the program is deployed with correct permissions."
+    )]
     HostConfiguration,
 
     #[error("Incorrect conditional instruction. Cannot read on an update action.")]
@@ -119,7 +122,23 @@ pub enum SdkError {
     #[error("Incorrect conditional instruction. Cannot update on a read action.")]
     UpdateOnReadAction,
 
-    #[error("Unknown error.")]
+    #[error("Incorrect conditional instruction. Can only delete on a delete action.")]
+    DeleteOnOtherAction,
+
+    #[error("Incorrect conditional instruction. Can only count on a read action.")]
+    CountOnOtherAction,
+
+    #[error("No rows found. The read expected at least one row matching the conditions.")]
+    NotFound,
+
+    /// The simulated invocation failed, holds the base64 XDR of the error `ScVal`.
+    #[error("Simulation failed with error {0}.")]
+    SimulationFailed(String),
+
+    #[error("Invalid pagination. The page size must be positive and the page offset must fit in an i64.")]
+    InvalidPagination,
+
+    #[error("Unknown error. The host returned an unexpected status.")]
     Unknown,
 }
 
@@ -131,11 +150,52 @@ impl SdkError {
             ZephyrStatus::DbWriteError => Err(SdkError::DbWrite),
             ZephyrStatus::NoValOnStack => Err(SdkError::NoValOnStack),
             ZephyrStatus::HostConfiguration => Err(SdkError::HostConfiguration),
+            ZephyrStatus::NotFound => Err(SdkError::NotFound),
             ZephyrStatus::Unknown => Err(SdkError::Unknown),
         }
     }
 }
 
+/// Lossy: permission and operator errors are all reported as
+/// `SdkError::HostConfiguration`, and duplicate keys as `SdkError::DbWrite`.
+impl From<DatabaseError> for SdkError {
+    fn from(value: DatabaseError) -> Self {
+        match value {
+            DatabaseError::WriteError | DatabaseError::DuplicateKey => SdkError::DbWrite,
+            DatabaseError::NotFound => SdkError::NotFound,
+            DatabaseError::ZephyrQueryError | DatabaseError::ZephyrQueryMalformed => {
+                SdkError::DbRead
+            }
+            DatabaseError::ReadOnWriteOnly
+            | DatabaseError::WriteOnReadOnly
+            | DatabaseError::OperatorError => SdkError::HostConfiguration,
+        }
+    }
+}
+
+/// Lossy: the statuses only cover the errors the host can report, so
+/// errors raised by the SDK itself (conversions, misused query wrappers and
+/// failed simulations) are all reported as `ZephyrStatus::Unknown`.
+impl From<SdkError> for ZephyrStatus {
+    fn from(value: SdkError) -> Self {
+        match value {
+            SdkError::DbRead => ZephyrStatus::DbReadError,
+            SdkError::DbWrite => ZephyrStatus::DbWriteError,
+            SdkError::NoValOnStack => ZephyrStatus::NoValOnStack,
+            SdkError::HostConfiguration => ZephyrStatus::HostConfiguration,
+            SdkError::NotFound => ZephyrStatus::NotFound,
+            SdkError::Conversion
+            | SdkError::ReadOnUpdateAction
+            | SdkError::UpdateOnReadAction
+            | SdkError::DeleteOnOtherAction
+            | SdkError::CountOnOtherAction
+            | SdkError::SimulationFailed(_)
+            | SdkError::InvalidPagination
+            | SdkError::Unknown => ZephyrStatus::Unknown,
+        }
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ContractDataEntryStellarXDR {
@@ -156,6 +216,24 @@ pub struct ContractDataEntry {
     pub last_modified: i32,
 }
 
+#[allow(missing_docs)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TrustlineEntryStellarXDR {
+    pub account_id: stellar_xdr::next::AccountId,
+    pub asset: stellar_xdr::next::TrustLineAsset,
+    pub entry: stellar_xdr::next::LedgerEntry,
+    pub last_modified: i32,
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OfferEntryStellarXDR {
+    pub seller_id: stellar_xdr::next::AccountId,
+    pub offer_id: i64,
+    pub entry: stellar_xdr::next::LedgerEntry,
+    pub last_modified: i32,
+}
+
 impl Into<ContractDataEntry> for ContractDataEntryStellarXDR {
     fn into(self) -> ContractDataEntry {
         ContractDataEntry {
@@ -183,3 +261,88 @@ impl Into<ContractDataEntry> for ContractDataEntryStellarXDR {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::SdkError;
+    use rs_zephyr_common::{DatabaseError, ZephyrStatus};
+
+    #[test]
+    pub fn database_error_mapping() {
+        let cases = [
+            (DatabaseError::ReadOnWriteOnly, SdkError::HostConfiguration),
+            (DatabaseError::WriteOnReadOnly, SdkError::HostConfiguration),
+            (DatabaseError::ZephyrQueryMalformed, SdkError::DbRead),
+            (DatabaseError::ZephyrQueryError, SdkError::DbRead),
+            (DatabaseError::WriteError, SdkError::DbWrite),
+            (DatabaseError::OperatorError, SdkError::HostConfiguration),
+            (DatabaseError::DuplicateKey, SdkError::DbWrite),
+            (DatabaseError::NotFound, SdkError::NotFound),
+        ];
+
+        for (database_error, expected) in cases {
+            let description = database_error.to_string();
+            let mapped = SdkError::from(database_error);
+
+            assert_eq!(
+                core::mem::discriminant(&mapped),
+                core::mem::discriminant(&expected),
+                "{} mapped to {:?}",
+                description,
+                mapped
+            );
+        }
+    }
+
+    #[test]
+    pub fn status_mapping() {
+        let cases = [
+            (SdkError::DbRead, ZephyrStatus::DbReadError),
+            (SdkError::DbWrite, ZephyrStatus::DbWriteError),
+            (SdkError::NoValOnStack, ZephyrStatus::NoValOnStack),
+            (SdkError::HostConfiguration, ZephyrStatus::HostConfiguration),
+            (SdkError::NotFound, ZephyrStatus::NotFound),
+            (SdkError::Conversion, ZephyrStatus::Unknown),
+            (SdkError::ReadOnUpdateAction, ZephyrStatus::Unknown),
+            (SdkError::UpdateOnReadAction, ZephyrStatus::Unknown),
+            (SdkError::DeleteOnOtherAction, ZephyrStatus::Unknown),
+            (SdkError::CountOnOtherAction, ZephyrStatus::Unknown),
+            (
+                SdkError::SimulationFailed("AAAAAQ==".into()),
+                ZephyrStatus::Unknown,
+            ),
+            (SdkError::InvalidPagination, ZephyrStatus::Unknown),
+            (SdkError::Unknown, ZephyrStatus::Unknown),
+        ];
+
+        for (error, expected) in cases {
+            let description = format!("{:?}", error);
+
+            assert_eq!(
+                ZephyrStatus::from(error) as u32,
+                expected as u32,
+                "{}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    pub fn status_roundtrip() {
+        assert!(SdkError::express_from_status(ZephyrStatus::Success as i64).is_ok());
+
+        for status in [
+            ZephyrStatus::DbReadError,
+            ZephyrStatus::DbWriteError,
+            ZephyrStatus::NoValOnStack,
+            ZephyrStatus::HostConfiguration,
+            ZephyrStatus::NotFound,
+            ZephyrStatus::Unknown,
+        ] {
+            let status = status as u32;
+            let error = SdkError::express_from_status(status as i64).unwrap_err();
+
+            assert_eq!(ZephyrStatus::from(error) as u32, status);
+        }
+    }
+}
@@ -41,4 +41,88 @@ impl EnvLogger {
 
         EnvClient::message_relay(RelayedMessageRequest::Log(log));
     }
+
+    /// Logs an info event to the environment.
+    pub fn info(&self, message: impl ToString, data: Option<Vec<u8>>) {
+        let log = ZephyrLog {
+            level: LogLevel::Info,
+            message: message.to_string(),
+            data,
+        };
+
+        EnvClient::message_relay(RelayedMessageRequest::Log(log));
+    }
+
+    /// Logs a debug event with the fields serialized as a JSON object
+    /// in the log's data, see [`ZephyrLog::with_kv`].
+    pub fn debug_structured(&self, message: impl ToString, fields: &[(&str, &str)]) {
+        self.log_structured(LogLevel::Debug, message, fields)
+    }
+
+    /// Logs an info event with the fields serialized as a JSON object
+    /// in the log's data, see [`ZephyrLog::with_kv`].
+    pub fn info_structured(&self, message: impl ToString, fields: &[(&str, &str)]) {
+        self.log_structured(LogLevel::Info, message, fields)
+    }
+
+    /// Logs an error with the fields serialized as a JSON object
+    /// in the log's data, see [`ZephyrLog::with_kv`].
+    pub fn error_structured(&self, message: impl ToString, fields: &[(&str, &str)]) {
+        self.log_structured(LogLevel::Error, message, fields)
+    }
+
+    /// Alias of [`EnvLogger::info_structured`].
+    pub fn info_kv(&self, message: impl ToString, fields: &[(&str, &str)]) {
+        self.info_structured(message, fields)
+    }
+
+    /// Alias of [`EnvLogger::debug_structured`].
+    pub fn debug_kv(&self, message: impl ToString, fields: &[(&str, &str)]) {
+        self.debug_structured(message, fields)
+    }
+
+    fn log_structured(&self, level: LogLevel, message: impl ToString, fields: &[(&str, &str)]) {
+        let log = structured_log(level, message, fields);
+        EnvClient::message_relay(RelayedMessageRequest::Log(log));
+    }
+}
+
+fn structured_log(level: LogLevel, message: impl ToString, fields: &[(&str, &str)]) -> ZephyrLog {
+    fields.iter().fold(
+        ZephyrLog {
+            level,
+            message: message.to_string(),
+            data: None,
+        },
+        |log, (key, value)| log.with_kv(key, value),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use rs_zephyr_common::log::LogLevel;
+
+    use super::structured_log;
+
+    #[test]
+    pub fn structured_levels() {
+        let levels = [
+            (LogLevel::Error, 0_u32),
+            (LogLevel::Warning, 1),
+            (LogLevel::Debug, 2),
+            (LogLevel::Info, 3),
+        ];
+
+        for (level, discriminant) in levels {
+            let log = structured_log(level.clone(), "indexed", &[("ledger", "2000")]);
+
+            let serialized = bincode::serialize(&log.level).unwrap();
+            assert_eq!(serialized, bincode::serialize(&discriminant).unwrap());
+            assert_eq!(log.message, "indexed");
+            assert_eq!(log.data.as_deref(), Some(&br#"{"ledger":"2000"}"#[..]));
+        }
+
+        let log = structured_log(LogLevel::Info, "indexed", &[]);
+        assert!(log.data.is_none());
+    }
 }
@@ -3,5 +3,11 @@
 //! Exports types and modules used under the hood by the Zephyr SDK's macros.
 //!
 
-pub use crate::{bincode, database::TableQueryWrapper, Condition, DatabaseInteract, ZephyrVal};
+pub use crate::{
+    bincode, database::TableQueryWrapper, utils::sha256, Condition, DatabaseInteract, ReadOptions,
+    TableRows, ZephyrVal,
+};
+pub use rs_zephyr_common::schema::{
+    table_ddl, Column as TomlColumn, ColumnSchema, Table as TomlTable, COLUMN_TYPE,
+};
 pub use soroban_sdk::xdr::{Limits, ReadXdr, WriteXdr};
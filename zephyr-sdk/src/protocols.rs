@@ -0,0 +1,38 @@
+//! Helpers for reading the state of Stellar protocols.
+
+use soroban_sdk::{
+    xdr::{Hash, ScAddress, ScVal},
+    Address, Symbol, Val,
+};
+
+use crate::{EnvClient, SdkError};
+
+pub mod phoenix;
+pub mod price_oracle;
+pub mod reflector;
+pub mod soroswap;
+
+/// Simulates calling `method` on `contract` from the `source` account
+/// and returns the function's return value.
+fn simulate_call(
+    env: &EnvClient,
+    source: &str,
+    contract: &Address,
+    method: &str,
+    args: soroban_sdk::Vec<Val>,
+) -> Result<ScVal, SdkError> {
+    let ScVal::Address(ScAddress::Contract(Hash(contract))) = env.to_scval(contract.clone()) else {
+        return Err(SdkError::Conversion);
+    };
+
+    let simulation = env.simulate_contract_call(
+        source.to_string(),
+        contract,
+        Symbol::new(env.soroban(), method),
+        args,
+    )?;
+
+    // Failed invocations are already returned as errors by
+    // `simulate_contract_call`.
+    simulation.invoke_result.map_err(|_| SdkError::Unknown)
+}
@@ -0,0 +1,116 @@
+//! Phoenix DEX helpers.
+
+use soroban_sdk::{
+    xdr::{LedgerEntryData, ScAddress, ScMapEntry, ScSymbol, ScVal},
+    Address,
+};
+
+use crate::{
+    utils::{instance_entries, parts_to_i128, to_datakey_u32},
+    EnvClient,
+};
+
+/// Reserves and shares of a Phoenix pool.
+#[derive(Clone, Debug)]
+#[allow(missing_docs)]
+pub struct PhoenixPool {
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub total_shares: i128,
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn find<'a>(entries: &'a [ScMapEntry], key: &ScVal) -> Option<&'a ScVal> {
+    entries
+        .iter()
+        .find(|entry| &entry.key == key)
+        .map(|entry| &entry.val)
+}
+
+/// Reads the pool from the contract's instance storage.
+///
+/// Mirrors `contracts/pool/src/storage.rs` in
+/// <https://github.com/Phoenix-Protocol-Group/phoenix-contracts>: the
+/// tokens are read from the `Config` stored under the `CONFIG` symbol,
+/// while the total shares and the reserves are stored under the
+/// `DataKey::TotalShares`, `DataKey::ReserveA` and `DataKey::ReserveB`
+/// keys, which the contract serializes as the `0`, `1` and `2` integers.
+pub fn get_pool(env: &EnvClient, pool_contract: [u8; 32]) -> Option<PhoenixPool> {
+    let instance = env.read_contract_instance(pool_contract).ok()??;
+    let LedgerEntryData::ContractData(data) = instance.entry.data else {
+        return None;
+    };
+    let entries = instance_entries(&data.val)?;
+
+    let Some(ScVal::Map(Some(config))) = find(&entries, &symbol("CONFIG")) else {
+        return None;
+    };
+    let token = |field: &str| match find(&config.0, &symbol(field)) {
+        Some(address @ ScVal::Address(ScAddress::Contract(_))) => Some(env.from_scval(address)),
+        _ => None,
+    };
+    let amount = |key: u32| match find(&entries, &to_datakey_u32(key)) {
+        Some(ScVal::I128(parts)) => Some(parts_to_i128(parts)),
+        _ => None,
+    };
+
+    Some(PhoenixPool {
+        token_a: token("token_a")?,
+        token_b: token("token_b")?,
+        total_shares: amount(0)?,
+        reserve_a: amount(1)?,
+        reserve_b: amount(2)?,
+    })
+}
+
+/// Returns the value of `lp_balance` pool shares given the prices of
+/// the two tokens, in the prices' unit.
+pub fn lp_value(pool: &PhoenixPool, lp_balance: i128, price_a: i128, price_b: i128) -> i128 {
+    if pool.total_shares <= 0 {
+        return 0;
+    }
+
+    let pool_value = pool.reserve_a * price_a + pool.reserve_b * price_b;
+    pool_value * lp_balance / pool.total_shares
+}
+
+/// Returns the output of swapping `amount_in` of token a (or token b when
+/// `from_a` is false) in a standard (`xyk`) pool.
+///
+/// Uses the constant-product formula and doesn't deduct the pool
+/// commission. Stable pools (the `pool_stable` contract) follow the
+/// StableSwap invariant and aren't supported.
+pub fn compute_standard_swap_output(pool: &PhoenixPool, amount_in: i128, from_a: bool) -> i128 {
+    if from_a {
+        standard_swap_output(pool.reserve_a, pool.reserve_b, amount_in)
+    } else {
+        standard_swap_output(pool.reserve_b, pool.reserve_a, amount_in)
+    }
+}
+
+fn standard_swap_output(reserve_in: i128, reserve_out: i128, amount_in: i128) -> i128 {
+    if amount_in <= 0 || reserve_in <= 0 || reserve_out <= 0 {
+        return 0;
+    }
+
+    amount_in * reserve_out / (reserve_in + amount_in)
+}
+
+#[cfg(test)]
+mod test {
+    use super::standard_swap_output;
+
+    #[test]
+    pub fn standard_swap() {
+        assert_eq!(standard_swap_output(1_000, 2_000, 1_000), 1_000);
+        assert_eq!(standard_swap_output(2_000, 1_000, 1_000), 333);
+        assert_eq!(standard_swap_output(1_000, 2_000, 0), 0);
+        assert_eq!(standard_swap_output(1_000, 2_000, -1), 0);
+        assert_eq!(standard_swap_output(0, 2_000, 1_000), 0);
+    }
+}
@@ -0,0 +1,194 @@
+//! Oracle-agnostic price reading.
+//!
+//! Programs that only need a price can accept a `&dyn PriceOracle` and
+//! work with any of the implementations below.
+
+use soroban_sdk::{
+    vec,
+    xdr::{ScSymbol, ScVal},
+    Address, IntoVal,
+};
+
+use super::{reflector::reflector_lastprice, simulate_call};
+use crate::{utils::parts_to_i128, EnvClient};
+
+/// A source of asset prices.
+pub trait PriceOracle {
+    /// Returns the latest price of `asset`, or `None` when the oracle
+    /// doesn't have one.
+    fn price(&self, env: &EnvClient, asset: Address) -> Option<i128>;
+
+    /// Returns the number of decimals of the prices.
+    fn decimals(&self) -> u32;
+}
+
+/// A Reflector oracle, prices are read with [`reflector_lastprice`].
+#[derive(Clone, Debug)]
+pub struct ReflectorOracle {
+    /// Account (`G...` strkey) the simulations are run from.
+    pub source: String,
+    /// The oracle contract.
+    pub oracle: Address,
+    /// Decimals of the oracle's prices, as returned by its `decimals`
+    /// function.
+    pub decimals: u32,
+}
+
+impl PriceOracle for ReflectorOracle {
+    fn price(&self, env: &EnvClient, asset: Address) -> Option<i128> {
+        let latest = reflector_lastprice(env, &self.source, &self.oracle, asset).ok()??;
+        Some(latest.price)
+    }
+
+    fn decimals(&self) -> u32 {
+        self.decimals
+    }
+}
+
+/// An oracle whose `method` takes the asset address and returns either the
+/// price or a struct with a `price` field, optionally wrapped in an
+/// `Option`.
+#[derive(Clone, Debug)]
+pub struct SimulatedOracle {
+    /// Account (`G...` strkey) the simulations are run from.
+    pub source: String,
+    /// The oracle contract.
+    pub contract: Address,
+    /// Name of the function returning the price.
+    pub method: String,
+    /// Decimals of the oracle's prices.
+    pub decimals: u32,
+}
+
+impl PriceOracle for SimulatedOracle {
+    fn price(&self, env: &EnvClient, asset: Address) -> Option<i128> {
+        let args = vec![env.soroban(), asset.into_val(env.soroban())];
+        let result = simulate_call(env, &self.source, &self.contract, &self.method, args).ok()?;
+
+        price_from_scval(&result)
+    }
+
+    fn decimals(&self) -> u32 {
+        self.decimals
+    }
+}
+
+/// Reads prices from `primary` and falls back to `fallback` when it
+/// doesn't have one.
+///
+/// Prices are expressed in the primary oracle's decimals, fallback prices
+/// are rescaled accordingly.
+pub struct ChainedOracle {
+    /// The oracle that is queried first.
+    pub primary: Box<dyn PriceOracle>,
+    /// The oracle that is queried when the primary has no price.
+    pub fallback: Box<dyn PriceOracle>,
+}
+
+impl PriceOracle for ChainedOracle {
+    fn price(&self, env: &EnvClient, asset: Address) -> Option<i128> {
+        chain_prices(
+            self.primary.price(env, asset.clone()),
+            self.primary.decimals(),
+            || Some((self.fallback.price(env, asset)?, self.fallback.decimals())),
+        )
+    }
+
+    fn decimals(&self) -> u32 {
+        self.primary.decimals()
+    }
+}
+
+fn chain_prices(
+    primary: Option<i128>,
+    decimals: u32,
+    fallback: impl FnOnce() -> Option<(i128, u32)>,
+) -> Option<i128> {
+    if primary.is_some() {
+        return primary;
+    }
+
+    let (price, fallback_decimals) = fallback()?;
+    rescale(price, fallback_decimals, decimals)
+}
+
+/// Converts `price` from `from` to `to` decimals, `None` on overflow.
+fn rescale(price: i128, from: u32, to: u32) -> Option<i128> {
+    if to >= from {
+        price.checked_mul(10_i128.checked_pow(to - from)?)
+    } else {
+        Some(price / 10_i128.checked_pow(from - to)?)
+    }
+}
+
+/// Extracts the price from an oracle's return value.
+fn price_from_scval(val: &ScVal) -> Option<i128> {
+    match val {
+        ScVal::I128(parts) => Some(parts_to_i128(parts)),
+        ScVal::Map(Some(map)) => {
+            let key = ScVal::Symbol(ScSymbol("price".try_into().unwrap()));
+            map.0
+                .iter()
+                .find(|entry| entry.key == key)
+                .and_then(|entry| price_from_scval(&entry.val))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use soroban_sdk::xdr::{Int128Parts, ScMap, ScMapEntry, ScSymbol, ScVal};
+
+    use super::{chain_prices, price_from_scval, rescale};
+
+    #[test]
+    pub fn chained_fallback() {
+        let called = Cell::new(false);
+        let fallback = || {
+            called.set(true);
+            Some((25, 1))
+        };
+
+        assert_eq!(chain_prices(Some(100), 2, fallback), Some(100));
+        assert!(!called.get());
+
+        assert_eq!(chain_prices(None, 2, fallback), Some(250));
+        assert!(called.get());
+
+        assert_eq!(chain_prices(None, 2, || None), None);
+    }
+
+    #[test]
+    pub fn decimals_rescaling() {
+        assert_eq!(rescale(12_345, 2, 4), Some(1_234_500));
+        assert_eq!(rescale(12_345, 4, 2), Some(123));
+        assert_eq!(rescale(12_345, 3, 3), Some(12_345));
+        assert_eq!(rescale(i128::MAX, 0, 1), None);
+    }
+
+    #[test]
+    pub fn price_extraction() {
+        let price = ScVal::I128(Int128Parts { hi: 0, lo: 42 });
+        let price_data = ScVal::Map(Some(ScMap(
+            vec![
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("price".try_into().unwrap())),
+                    val: price.clone(),
+                },
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("timestamp".try_into().unwrap())),
+                    val: ScVal::U64(1_700_000_000),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        )));
+
+        assert_eq!(price_from_scval(&price), Some(42));
+        assert_eq!(price_from_scval(&price_data), Some(42));
+        assert_eq!(price_from_scval(&ScVal::Void), None);
+    }
+}
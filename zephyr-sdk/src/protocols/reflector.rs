@@ -0,0 +1,246 @@
+//! [Reflector](https://reflector.network) oracle helpers.
+
+use soroban_sdk::{
+    vec,
+    xdr::{Hash, ScAddress, ScSymbol, ScVal},
+    Address, IntoVal, Symbol, Val,
+};
+use thiserror::Error;
+
+use super::simulate_call;
+use crate::{utils::parts_to_i128, EnvClient, SdkError};
+
+/// A price record of the oracle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceData {
+    /// The price, in the oracle's decimals.
+    pub price: i128,
+    /// Timestamp of the record, in seconds.
+    pub timestamp: u64,
+}
+
+/// Errors of the Reflector helpers.
+#[derive(Clone, Debug, Error)]
+pub enum ReflectorError {
+    /// The simulated oracle call failed.
+    #[error("Oracle call failed: {0}")]
+    Sdk(#[from] SdkError),
+
+    /// The oracle has no price for the asset.
+    #[error("The oracle has no price for the asset.")]
+    NoPrice,
+
+    /// The latest price record is older than the allowed age.
+    #[error("The latest price is {age} seconds old.")]
+    StalePrice {
+        /// Age of the latest record, in seconds.
+        age: u64,
+    },
+}
+
+/// Returns the latest price record of `asset` through the oracle's
+/// `lastprice` function, simulated from the `source` account.
+pub fn reflector_lastprice(
+    env: &EnvClient,
+    source: &str,
+    oracle: &Address,
+    asset: Address,
+) -> Result<Option<PriceData>, SdkError> {
+    let args = vec![env.soroban(), stellar_asset(env, asset)];
+    let result = simulate_call(env, source, oracle, "lastprice", args)?;
+
+    Ok(price_data_from_scval(&result))
+}
+
+/// Returns the time-weighted average price of `asset` over the last
+/// `resolution` periods of the oracle (each lasting the oracle's
+/// `resolution()` seconds), through its `twap` function.
+///
+/// Falls back to the latest price when the oracle doesn't support TWAP or
+/// doesn't have enough records. Returns [`ReflectorError::StalePrice`]
+/// when the latest record is more than `max_age_seconds` older than the
+/// current ledger.
+pub fn reflector_twap(
+    env: &EnvClient,
+    source: &str,
+    oracle: Address,
+    asset: Address,
+    resolution: u32,
+    max_age_seconds: u64,
+) -> Result<i128, ReflectorError> {
+    let latest =
+        reflector_lastprice(env, source, &oracle, asset.clone())?.ok_or(ReflectorError::NoPrice)?;
+    check_age(&latest, env.soroban().ledger().timestamp(), max_age_seconds)?;
+
+    let args = vec![
+        env.soroban(),
+        stellar_asset(env, asset),
+        resolution.into_val(env.soroban()),
+    ];
+    let twap = match simulate_call(env, source, &oracle, "twap", args) {
+        Ok(ScVal::I128(parts)) => Some(parts_to_i128(&parts)),
+        _ => None,
+    };
+
+    Ok(twap.unwrap_or(latest.price))
+}
+
+/// Client of a Reflector oracle, reads prices from the same `source`
+/// account without decoding the oracle address on every call.
+#[derive(Clone, Debug)]
+pub struct ReflectorClient {
+    oracle: [u8; 32],
+    source: String,
+}
+
+impl ReflectorClient {
+    /// Creates a client of the oracle with the `C...` strkey
+    /// `oracle_address`, simulating calls from the `G...` `source` account.
+    pub fn new(oracle_address: &str, source: &str) -> Result<Self, SdkError> {
+        let oracle = stellar_strkey::Contract::from_string(oracle_address)
+            .map_err(|_| SdkError::Conversion)?;
+
+        Ok(Self {
+            oracle: oracle.0,
+            source: source.to_string(),
+        })
+    }
+
+    /// Returns the address of the oracle contract.
+    pub fn oracle(&self, env: &EnvClient) -> Result<Address, SdkError> {
+        env.try_from_scval(&ScVal::Address(ScAddress::Contract(Hash(self.oracle))))
+    }
+
+    /// Returns the latest price of `asset`.
+    pub fn price(&self, env: &EnvClient, asset: Address) -> Result<i128, ReflectorError> {
+        let oracle = self.oracle(env)?;
+        let latest = reflector_lastprice(env, &self.source, &oracle, asset)?;
+
+        Ok(latest.ok_or(ReflectorError::NoPrice)?.price)
+    }
+
+    /// Returns the latest price of each of `assets`, in the same order.
+    ///
+    /// Reflector's `prices` function returns the history of a single asset,
+    /// so the oracle has no batched read and `lastprice` is simulated for
+    /// each asset. Errors with the first asset that has no price.
+    pub fn prices_batch(
+        &self,
+        env: &EnvClient,
+        assets: &[Address],
+    ) -> Result<Vec<i128>, ReflectorError> {
+        let oracle = self.oracle(env)?;
+
+        assets
+            .iter()
+            .map(|asset| {
+                let latest = reflector_lastprice(env, &self.source, &oracle, asset.clone())?;
+                Ok(latest.ok_or(ReflectorError::NoPrice)?.price)
+            })
+            .collect()
+    }
+}
+
+/// Builds the oracle's `Asset::Stellar(Address)` argument.
+fn stellar_asset(env: &EnvClient, asset: Address) -> Val {
+    let variant: Val = Symbol::new(env.soroban(), "Stellar").into_val(env.soroban());
+    let address: Val = asset.into_val(env.soroban());
+
+    vec![env.soroban(), variant, address].into_val(env.soroban())
+}
+
+fn check_age(latest: &PriceData, now: u64, max_age_seconds: u64) -> Result<(), ReflectorError> {
+    let age = now.saturating_sub(latest.timestamp);
+    if age > max_age_seconds {
+        return Err(ReflectorError::StalePrice { age });
+    }
+
+    Ok(())
+}
+
+fn price_data_from_scval(val: &ScVal) -> Option<PriceData> {
+    let ScVal::Map(Some(map)) = val else {
+        return None;
+    };
+    let field = |name: &str| {
+        let key = ScVal::Symbol(ScSymbol(name.try_into().unwrap()));
+        map.0
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.val)
+    };
+
+    let (Some(ScVal::I128(price)), Some(ScVal::U64(timestamp))) =
+        (field("price"), field("timestamp"))
+    else {
+        return None;
+    };
+
+    Some(PriceData {
+        price: parts_to_i128(price),
+        timestamp: *timestamp,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use soroban_sdk::xdr::{Int128Parts, ScMap, ScMapEntry, ScSymbol, ScVal};
+
+    use super::{check_age, price_data_from_scval, PriceData, ReflectorClient, ReflectorError};
+
+    fn price_data(price: u64, timestamp: u64) -> ScVal {
+        ScVal::Map(Some(ScMap(
+            vec![
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("price".try_into().unwrap())),
+                    val: ScVal::I128(Int128Parts { hi: 0, lo: price }),
+                },
+                ScMapEntry {
+                    key: ScVal::Symbol(ScSymbol("timestamp".try_into().unwrap())),
+                    val: ScVal::U64(timestamp),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        )))
+    }
+
+    #[test]
+    pub fn price_data_parsing() {
+        assert_eq!(
+            price_data_from_scval(&price_data(42, 1_700_000_000)),
+            Some(PriceData {
+                price: 42,
+                timestamp: 1_700_000_000,
+            })
+        );
+        assert_eq!(price_data_from_scval(&ScVal::Void), None);
+    }
+
+    #[test]
+    pub fn staleness() {
+        let latest = PriceData {
+            price: 42,
+            timestamp: 1_000,
+        };
+
+        assert!(check_age(&latest, 1_300, 300).is_ok());
+        assert!(check_age(&latest, 900, 300).is_ok());
+        assert!(matches!(
+            check_age(&latest, 1_301, 300),
+            Err(ReflectorError::StalePrice { age: 301 })
+        ));
+    }
+
+    #[test]
+    pub fn client_oracle_address() {
+        let oracle = stellar_strkey::Contract([7; 32]).to_string();
+        let source = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+        let client = ReflectorClient::new(&oracle, source).unwrap();
+        assert_eq!(client.oracle, [7; 32]);
+        assert_eq!(client.source, source);
+
+        assert!(ReflectorClient::new(source, source).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Soroswap AMM helpers.
+
+use soroban_sdk::{
+    xdr::{Hash, LedgerEntryData, ScAddress, ScSymbol, ScVal, ScVec},
+    Address,
+};
+
+use crate::{
+    utils::{instance_entries, parts_to_i128, to_datakey_u32},
+    EnvClient,
+};
+
+/// Reserves of a Soroswap pair.
+#[derive(Clone, Debug)]
+#[allow(missing_docs)]
+pub struct SoroswapPool {
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+}
+
+fn contract_address(contract: [u8; 32]) -> ScVal {
+    ScVal::Address(ScAddress::Contract(Hash(contract)))
+}
+
+/// Reads the pair of two tokens (`C...` strkeys) through the factory's
+/// storage, `token_a` and `reserve_a` refer to the first provided token.
+///
+/// Mirrors the storage of <https://github.com/soroswap/core>: the factory
+/// (`contracts/factory/src/storage.rs`) indexes pairs under
+/// `DataKey::PairAddressesByTokens(Pair(token_0, token_1))` with sorted
+/// tokens, and the pair (`contracts/pair/src/storage.rs`) keeps `Token0`,
+/// `Reserve0` and `Reserve1` in its instance storage under a `#[repr(u32)]`
+/// `DataKey`, serialized as the `0`, `2` and `3` integers.
+///
+/// Returns `None` when any of the tokens is invalid or the pair doesn't
+/// exist.
+pub fn get_pool(
+    env: &EnvClient,
+    factory: [u8; 32],
+    token_a: &str,
+    token_b: &str,
+) -> Option<SoroswapPool> {
+    let a = stellar_strkey::Contract::from_string(token_a).ok()?.0;
+    let b = stellar_strkey::Contract::from_string(token_b).ok()?.0;
+
+    let (token_0, token_1) = if a < b { (a, b) } else { (b, a) };
+    let pair_tokens = ScVal::Vec(Some(ScVec(
+        vec![contract_address(token_0), contract_address(token_1)]
+            .try_into()
+            .unwrap(),
+    )));
+    let key = ScVal::Vec(Some(ScVec(
+        vec![
+            ScVal::Symbol(ScSymbol("PairAddressesByTokens".try_into().unwrap())),
+            pair_tokens,
+        ]
+        .try_into()
+        .unwrap(),
+    )));
+
+    let pair_entry = env.read_contract_entry_by_scvalkey(factory, key).ok()??;
+    let LedgerEntryData::ContractData(pair_data) = pair_entry.entry.data else {
+        return None;
+    };
+    let ScVal::Address(ScAddress::Contract(pair)) = pair_data.val else {
+        return None;
+    };
+
+    let instance = env.read_contract_instance(pair.0).ok()??;
+    let LedgerEntryData::ContractData(instance_data) = instance.entry.data else {
+        return None;
+    };
+    let entries = instance_entries(&instance_data.val)?;
+    let field = |key: u32| {
+        entries
+            .iter()
+            .find(|entry| entry.key == to_datakey_u32(key))
+            .map(|entry| &entry.val)
+    };
+    let reserve = |key: u32| match field(key) {
+        Some(ScVal::I128(parts)) => Some(parts_to_i128(parts)),
+        _ => None,
+    };
+
+    let (reserve_0, reserve_1) = (reserve(2)?, reserve(3)?);
+    let (reserve_a, reserve_b) = if field(0)? == &contract_address(a) {
+        (reserve_0, reserve_1)
+    } else {
+        (reserve_1, reserve_0)
+    };
+
+    Some(SoroswapPool {
+        token_a: env.from_scval(&contract_address(a)),
+        token_b: env.from_scval(&contract_address(b)),
+        reserve_a,
+        reserve_b,
+    })
+}
+
+/// Returns the output of swapping `input_amount` of token a (or token b
+/// when `input_is_a` is false) in the pool, see [`get_amount_out`].
+pub fn compute_swap_output(pool: &SoroswapPool, input_amount: i128, input_is_a: bool) -> i128 {
+    if input_is_a {
+        get_amount_out(input_amount, pool.reserve_a, pool.reserve_b)
+    } else {
+        get_amount_out(input_amount, pool.reserve_b, pool.reserve_a)
+    }
+}
+
+/// Returns the output of swapping `amount_in` against the given reserves,
+/// using the constant-product formula with Soroswap's 0.3% fee.
+///
+/// Returns 0 when the amount or any of the reserves isn't positive.
+pub fn get_amount_out(amount_in: i128, reserve_in: i128, reserve_out: i128) -> i128 {
+    if amount_in <= 0 || reserve_in <= 0 || reserve_out <= 0 {
+        return 0;
+    }
+
+    let amount_in_with_fee = amount_in * 997;
+    (amount_in_with_fee * reserve_out) / (reserve_in * 1000 + amount_in_with_fee)
+}
+
+#[cfg(test)]
+mod test {
+    use super::get_amount_out;
+
+    #[test]
+    pub fn amount_out() {
+        assert_eq!(get_amount_out(1_000, 1_000_000, 1_000_000), 996);
+        assert_eq!(get_amount_out(1_000, 1_000_000, 2_000_000), 1_992);
+        assert_eq!(get_amount_out(1_000_000, 1_000_000, 1_000_000), 499_248);
+    }
+
+    #[test]
+    pub fn amount_out_without_liquidity() {
+        assert_eq!(get_amount_out(0, 1_000, 1_000), 0);
+        assert_eq!(get_amount_out(-1, 1_000, 1_000), 0);
+        assert_eq!(get_amount_out(1_000, 0, 1_000), 0);
+        assert_eq!(get_amount_out(1_000, 1_000, 0), 0);
+    }
+}
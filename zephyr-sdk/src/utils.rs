@@ -1,8 +1,36 @@
 //! Utilities for working with common data patterns.
 //! 
-use soroban_sdk::xdr::{Int128Parts, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, VecM};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    Int128Parts, Limits, ReadXdr, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, VecM, WriteXdr,
+};
 use crate::{EnvClient, SdkError};
 
+/// Network id of the Stellar testnet, i.e. the SHA-256 hash of
+/// `"Test SDF Network ; September 2015"`.
+pub const TESTNET_NETWORK_ID: [u8; 32] = [
+    0xce, 0xe0, 0x30, 0x2d, 0x59, 0x84, 0x4d, 0x32,
+    0xbd, 0xca, 0x91, 0x5c, 0x82, 0x03, 0xdd, 0x44,
+    0xb3, 0x3f, 0xbb, 0x7e, 0xdc, 0x19, 0x05, 0x1e,
+    0xa3, 0x7a, 0xbe, 0xdf, 0x28, 0xec, 0xd4, 0x72,
+];
+
+/// Network id of the Stellar mainnet, i.e. the SHA-256 hash of
+/// `"Public Global Stellar Network ; September 2015"`.
+pub const MAINNET_NETWORK_ID: [u8; 32] = [
+    0x7a, 0xc3, 0x39, 0x97, 0x54, 0x4e, 0x31, 0x75,
+    0xd2, 0x66, 0xbd, 0x02, 0x24, 0x39, 0xb2, 0x2c,
+    0xdb, 0x16, 0x50, 0x8c, 0x01, 0x16, 0x3f, 0x26,
+    0xe5, 0xcb, 0x2a, 0x3e, 0x10, 0x45, 0xa9, 0x79,
+];
+
+/// Returns the SHA-256 digest of the provided bytes.
+///
+/// Computed in the guest, without relying on Soroban host functions.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
 /// Returns an allocated String object starting from a Soroban SDK Address object.
 pub fn address_to_alloc_string(env: &EnvClient, address: soroban_sdk::Address) -> String {
     soroban_string_to_alloc_string(env, address.to_string())
@@ -58,3 +86,295 @@ pub fn to_scval_symbol(from: &str) -> Result<ScVal, SdkError> {
         from.try_into().map_err(|_| SdkError::Conversion)?,
     )))
 }
+
+/// Formats a fixed-point amount with `decimals` decimal places, e.g.
+/// `10_000_000` with 7 decimals is `"1.0000000"`.
+pub fn i128_to_decimal_string(amount: i128, decimals: u32) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let decimals = decimals as usize;
+    let sign = if amount < 0 { "-" } else { "" };
+
+    if decimals == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Parses a decimal string into a fixed-point amount with `decimals` decimal
+/// places, e.g. `"1.5"` with 7 decimals is `15_000_000`.
+///
+/// Errors with [`SdkError::Conversion`] when the string is not a decimal
+/// number, has more than `decimals` decimal places or overflows an `i128`.
+pub fn decimal_string_to_i128(s: &str, decimals: u32) -> Result<i128, SdkError> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let decimals = decimals as usize;
+
+    if integer.is_empty()
+        || fraction.len() > decimals
+        || (unsigned.contains('.') && fraction.is_empty())
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(SdkError::Conversion);
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+    let magnitude: u128 = digits.parse().map_err(|_| SdkError::Conversion)?;
+
+    if negative {
+        if magnitude > i128::MIN.unsigned_abs() {
+            return Err(SdkError::Conversion);
+        }
+        Ok((magnitude as i128).wrapping_neg())
+    } else {
+        i128::try_from(magnitude).map_err(|_| SdkError::Conversion)
+    }
+}
+
+/// Decodes a contract strkey (`C...`) into the contract id bytes.
+pub fn strkey_to_contract_bytes(strkey: &str) -> Result<[u8; 32], SdkError> {
+    Ok(stellar_strkey::Contract::from_string(strkey)
+        .map_err(|_| SdkError::Conversion)?
+        .0)
+}
+
+/// Encodes contract id bytes as a contract strkey (`C...`).
+pub fn contract_bytes_to_strkey(bytes: [u8; 32]) -> String {
+    stellar_strkey::Contract(bytes).to_string()
+}
+
+/// Decodes an ed25519 public key strkey (`G...`) into the key bytes.
+pub fn pubkey_strkey_to_bytes(strkey: &str) -> Result<[u8; 32], SdkError> {
+    Ok(stellar_strkey::ed25519::PublicKey::from_string(strkey)
+        .map_err(|_| SdkError::Conversion)?
+        .0)
+}
+
+/// Encodes ed25519 public key bytes as a strkey (`G...`).
+pub fn pubkey_bytes_to_strkey(bytes: [u8; 32]) -> String {
+    stellar_strkey::ed25519::PublicKey(bytes).to_string()
+}
+
+/// Decodes a base64 XDR string into `T`, e.g. an `ScVal` or a `Transaction`.
+pub fn parse_xdr_base64<T: ReadXdr>(s: &str) -> Result<T, SdkError> {
+    T::from_xdr_base64(s, Limits::none()).map_err(|_| SdkError::Conversion)
+}
+
+/// Encodes `val` as a base64 XDR string.
+pub fn to_xdr_base64<T: WriteXdr>(val: &T) -> String {
+    val.to_xdr_base64(Limits::none()).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        contract_bytes_to_strkey, decimal_string_to_i128, i128_to_decimal_string, parse_xdr_base64,
+        pubkey_bytes_to_strkey, pubkey_strkey_to_bytes, strkey_to_contract_bytes, to_xdr_base64,
+    };
+    use crate::SdkError;
+    use soroban_sdk::xdr::{
+        Hash, Int128Parts, LedgerCloseMeta, LedgerCloseMetaV0, LedgerHeader, LedgerHeaderExt,
+        LedgerHeaderHistoryEntry, LedgerHeaderHistoryEntryExt, Memo, MuxedAccount, Operation,
+        OperationBody, Preconditions, ScSymbol, ScVal, SequenceNumber, StellarValue,
+        StellarValueExt, TimePoint, Transaction, TransactionExt, TransactionSet, Uint256, VecM,
+    };
+
+    #[test]
+    pub fn decimal_formatting() {
+        assert_eq!(i128_to_decimal_string(10_000_000, 7), "1.0000000");
+        assert_eq!(i128_to_decimal_string(0, 7), "0.0000000");
+        assert_eq!(i128_to_decimal_string(-5, 7), "-0.0000005");
+        assert_eq!(i128_to_decimal_string(-123, 0), "-123");
+        assert_eq!(
+            i128_to_decimal_string(i128::MAX, 7),
+            "17014118346046923173168730371588.4105727"
+        );
+        assert_eq!(
+            i128_to_decimal_string(i128::MIN, 7),
+            "-17014118346046923173168730371588.4105728"
+        );
+    }
+
+    #[test]
+    pub fn decimal_parsing() {
+        assert_eq!(decimal_string_to_i128("1.5", 7).unwrap(), 15_000_000);
+        assert_eq!(decimal_string_to_i128("007", 2).unwrap(), 700);
+        assert_eq!(decimal_string_to_i128("-0.0000005", 7).unwrap(), -5);
+
+        for invalid in ["1.234", "1.", ".1", "+1", "-", "1e3", ""] {
+            assert!(matches!(
+                decimal_string_to_i128(invalid, 2),
+                Err(SdkError::Conversion)
+            ));
+        }
+        assert!(matches!(
+            decimal_string_to_i128("170141183460469231731687303715884105728", 0),
+            Err(SdkError::Conversion)
+        ));
+    }
+
+    #[test]
+    pub fn decimal_roundtrip() {
+        for amount in [0, 1, -1, 10_000_000, -123_456_789, i128::MAX, i128::MIN] {
+            for decimals in [0, 1, 7, 18, 40] {
+                let formatted = i128_to_decimal_string(amount, decimals);
+                assert_eq!(
+                    decimal_string_to_i128(&formatted, decimals).unwrap(),
+                    amount
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn strkey_roundtrip() {
+        let bytes: [u8; 32] = core::array::from_fn(|i| i as u8);
+
+        let contract = contract_bytes_to_strkey(bytes);
+        assert_eq!(
+            contract,
+            "CAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB6N4O"
+        );
+        assert_eq!(strkey_to_contract_bytes(&contract).unwrap(), bytes);
+
+        let pubkey = pubkey_bytes_to_strkey(bytes);
+        assert_eq!(
+            pubkey,
+            "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX"
+        );
+        assert_eq!(pubkey_strkey_to_bytes(&pubkey).unwrap(), bytes);
+    }
+
+    #[test]
+    pub fn invalid_strkeys() {
+        let contract = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+        let pubkey = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+        assert_eq!(strkey_to_contract_bytes(contract).unwrap(), [0; 32]);
+        assert_eq!(pubkey_strkey_to_bytes(pubkey).unwrap(), [0; 32]);
+
+        // Wrong version byte, bad checksum and garbage.
+        for invalid in [
+            pubkey,
+            "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC5",
+            "C",
+            "",
+        ] {
+            assert!(matches!(
+                strkey_to_contract_bytes(invalid),
+                Err(SdkError::Conversion)
+            ));
+        }
+        for invalid in [
+            contract,
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHG",
+            "not a key",
+        ] {
+            assert!(matches!(
+                pubkey_strkey_to_bytes(invalid),
+                Err(SdkError::Conversion)
+            ));
+        }
+    }
+
+    #[test]
+    pub fn scval_base64_roundtrip() {
+        let values = [
+            ScVal::I128(Int128Parts { hi: -1, lo: 42 }),
+            ScVal::Symbol(ScSymbol("balance".try_into().unwrap())),
+            ScVal::Void,
+        ];
+
+        for value in values {
+            let encoded = to_xdr_base64(&value);
+            assert_eq!(parse_xdr_base64::<ScVal>(&encoded).unwrap(), value);
+        }
+
+        assert_eq!(to_xdr_base64(&ScVal::Void), "AAAAAQ==");
+        assert!(matches!(
+            parse_xdr_base64::<ScVal>("not base64"),
+            Err(SdkError::Conversion)
+        ));
+    }
+
+    #[test]
+    pub fn transaction_base64_roundtrip() {
+        let transaction = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(7),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::Inflation,
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        };
+
+        let encoded = to_xdr_base64(&transaction);
+        assert_eq!(
+            parse_xdr_base64::<Transaction>(&encoded).unwrap(),
+            transaction
+        );
+
+        // Valid base64 that isn't a transaction.
+        assert!(matches!(
+            parse_xdr_base64::<Transaction>(&to_xdr_base64(&ScVal::Void)),
+            Err(SdkError::Conversion)
+        ));
+    }
+
+    #[test]
+    pub fn ledger_close_meta_base64_roundtrip() {
+        let meta = LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+            ledger_header: LedgerHeaderHistoryEntry {
+                hash: Hash([2; 32]),
+                header: LedgerHeader {
+                    ledger_version: 19,
+                    previous_ledger_hash: Hash([1; 32]),
+                    scp_value: StellarValue {
+                        tx_set_hash: Hash([0; 32]),
+                        close_time: TimePoint(1_700_000_000),
+                        upgrades: VecM::default(),
+                        ext: StellarValueExt::Basic,
+                    },
+                    tx_set_result_hash: Hash([0; 32]),
+                    bucket_list_hash: Hash([0; 32]),
+                    ledger_seq: 2000,
+                    total_coins: 1_000_000_000,
+                    fee_pool: 0,
+                    inflation_seq: 0,
+                    id_pool: 0,
+                    base_fee: 100,
+                    base_reserve: 5_000_000,
+                    max_tx_set_size: 100,
+                    skip_list: core::array::from_fn(|_| Hash([0; 32])),
+                    ext: LedgerHeaderExt::V0,
+                },
+                ext: LedgerHeaderHistoryEntryExt::V0,
+            },
+            tx_set: TransactionSet {
+                previous_ledger_hash: Hash([1; 32]),
+                txs: VecM::default(),
+            },
+            tx_processing: VecM::default(),
+            upgrades_processing: VecM::default(),
+            scp_info: VecM::default(),
+        });
+
+        let encoded = to_xdr_base64(&meta);
+        assert_eq!(parse_xdr_base64::<LedgerCloseMeta>(&encoded).unwrap(), meta);
+    }
+}